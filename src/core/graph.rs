@@ -36,16 +36,24 @@ pub type NodeIndexType = usize;
 /// }
 /// # path(2);
 /// ```
-pub struct Graph<Ty>(petgraph::Graph<(), (), Ty, NodeIndexType>)
+///
+/// Graphs may also carry a community (see [`communities`](Self#communities))
+/// and a position (see [`positions`](Self#positions)) for each of their nodes.
+pub struct Graph<Ty>
 where
-    Ty: EdgeType;
+    Ty: EdgeType,
+{
+    inner: petgraph::Graph<(), (), Ty, NodeIndexType>,
+    communities: Option<Vec<usize>>,
+    positions: Option<Vec<Vec<f64>>>,
+}
 
 impl<Ty> Default for Graph<Ty>
 where
     Ty: EdgeType,
 {
     fn default() -> Self {
-        Self(petgraph::Graph::<(), (), Ty, NodeIndexType>::default())
+        petgraph::Graph::<(), (), Ty, NodeIndexType>::default().into()
     }
 }
 
@@ -69,7 +77,7 @@ where
     ///
     /// These capacity are only size hints; they can improve performance but a graph built by this method can handle any number of nodes and edges.
     pub fn with_capacity(n_nodes: usize, n_edges: usize) -> Self {
-        Self(petgraph::Graph::with_capacity(n_nodes, n_edges))
+        petgraph::Graph::with_capacity(n_nodes, n_edges).into()
     }
 
     /// Adds a new node to the graph, using the lowest free positive integer label.
@@ -86,12 +94,14 @@ where
     /// assert_eq!(1, graph.n_nodes());
     /// ```
     pub fn new_node(&mut self) {
-        self.0.add_node(());
+        self.communities = None;
+        self.positions = None;
+        self.inner.add_node(());
     }
 
     /// Returns the number of nodes contained in the graph.
     pub fn n_nodes(&self) -> usize {
-        self.0.node_count()
+        self.inner.node_count()
     }
 
     /// Adds an edge to the graph.
@@ -111,19 +121,15 @@ where
     /// assert_eq!(4, graph.n_nodes());
     /// ```
     pub fn new_edge(&mut self, from: NodeIndexType, to: NodeIndexType) {
-        (self.n_nodes()..=from).for_each(|_| {
-            self.0.add_node(());
-        });
-        (self.n_nodes()..=to).for_each(|_| {
-            self.0.add_node(());
-        });
-        self.0
+        (self.n_nodes()..=from).for_each(|_| self.new_node());
+        (self.n_nodes()..=to).for_each(|_| self.new_node());
+        self.inner
             .add_edge(NodeIndex::from(from), NodeIndex::from(to), ());
     }
 
     /// Returns the number of edges contained in the graph.
     pub fn n_edges(&self) -> usize {
-        self.0.edge_count()
+        self.inner.edge_count()
     }
 
     /// Returns an iterator to the edges of this graph.
@@ -143,7 +149,7 @@ where
     /// # debug_graph(&Graph::<petgraph::Directed>::default());
     /// ```
     pub fn iter_edges(&self) -> impl Iterator<Item = (NodeIndexType, NodeIndexType)> + '_ {
-        self.0
+        self.inner
             .raw_edges()
             .iter()
             .map(|e| (e.source().index(), e.target().index()))
@@ -156,30 +162,106 @@ where
     /// If the provided nodes do not match any edge, this function panics.
     pub fn remove_edge(&mut self, from: NodeIndexType, to: NodeIndexType) {
         let index = self
-            .0
+            .inner
             .find_edge(from.into(), to.into())
             .unwrap_or_else(|| panic!("no such edge (from {} to {})", from, to));
-        self.0.remove_edge(index).unwrap();
+        self.inner.remove_edge(index).unwrap();
+    }
+
+    /// Returns the community of each node, if communities were set for this graph.
+    ///
+    /// Communities are given as a vector indexed by the node labels.
+    ///
+    /// ```
+    /// # use crusti_g2io::Graph;
+    /// use petgraph::Undirected;
+    ///
+    /// let mut graph = Graph::<Undirected>::with_capacity(3, 0);
+    /// (0..3).for_each(|_| graph.new_node());
+    /// assert_eq!(None, graph.communities());
+    /// graph.set_communities(vec![0, 0, 1]);
+    /// assert_eq!(Some([0, 0, 1].as_slice()), graph.communities());
+    /// graph.new_node();
+    /// assert_eq!(None, graph.communities());
+    /// ```
+    pub fn communities(&self) -> Option<&[usize]> {
+        self.communities.as_deref()
+    }
+
+    /// Sets the community of each node.
+    ///
+    /// Communities should be set once all the nodes are added, since adding a node discards them.
+    ///
+    /// # Panics
+    ///
+    /// If the number of communities does not match the number of nodes, this function panics.
+    pub fn set_communities(&mut self, communities: Vec<usize>) {
+        assert_eq!(
+            self.n_nodes(),
+            communities.len(),
+            "the number of communities must match the number of nodes"
+        );
+        self.communities = Some(communities);
+    }
+
+    /// Returns the position of each node, if positions were set for this graph.
+    ///
+    /// Positions are given as a vector indexed by the node labels; each position is a vector of coordinates.
+    pub fn positions(&self) -> Option<&[Vec<f64>]> {
+        self.positions.as_deref()
     }
 
+    /// Sets the position of each node.
+    ///
+    /// Positions should be set once all the nodes are added, since adding a node discards them.
+    ///
+    /// # Panics
+    ///
+    /// If the number of positions does not match the number of nodes, this function panics.
+    pub fn set_positions(&mut self, positions: Vec<Vec<f64>>) {
+        assert_eq!(
+            self.n_nodes(),
+            positions.len(),
+            "the number of positions must match the number of nodes"
+        );
+        self.positions = Some(positions);
+    }
+
+    /// Appends a graph to this one, shifting its node labels by the number of nodes of this graph.
+    ///
+    /// Communities are kept only if both graphs have some (or if this graph is empty);
+    /// in this case, the community indices of the appended graph are shifted to follow the existing ones.
+    /// The same applies to positions, which are kept unchanged.
     pub(crate) fn append_graph(&mut self, g: &Graph<Ty>) {
         let self_n_nodes = self.n_nodes();
+        let communities = match (&self.communities, &g.communities) {
+            (Some(c0), Some(c1)) => Some(append_communities(c0, c1)),
+            (None, Some(c1)) if self_n_nodes == 0 => Some(c1.clone()),
+            _ => None,
+        };
+        let positions = match (&self.positions, &g.positions) {
+            (Some(p0), Some(p1)) => Some([p0.as_slice(), p1.as_slice()].concat()),
+            (None, Some(p1)) if self_n_nodes == 0 => Some(p1.clone()),
+            _ => None,
+        };
         let g_n_nodes = g.n_nodes();
-        self.0.reserve_nodes(g_n_nodes);
+        self.inner.reserve_nodes(g_n_nodes);
         (0..g_n_nodes).for_each(|_| {
-            self.0.add_node(());
+            self.inner.add_node(());
         });
-        self.0.reserve_edges(g.n_edges());
-        for edge in g.0.raw_edges() {
+        self.inner.reserve_edges(g.n_edges());
+        for edge in g.inner.raw_edges() {
             self.new_edge(
                 edge.source().index() + self_n_nodes,
                 edge.target().index() + self_n_nodes,
             );
         }
+        self.communities = communities;
+        self.positions = positions;
     }
 
     pub(crate) fn petgraph(&self) -> &petgraph::Graph<(), (), Ty, NodeIndexType> {
-        &self.0
+        &self.inner
     }
 }
 
@@ -226,10 +308,22 @@ where
     Ty: EdgeType,
 {
     fn from(g: petgraph::Graph<(), (), Ty, NodeIndexType>) -> Self {
-        Self(g)
+        Self {
+            inner: g,
+            communities: None,
+            positions: None,
+        }
     }
 }
 
+fn append_communities(c0: &[usize], c1: &[usize]) -> Vec<usize> {
+    let offset = c0.iter().max().map_or(0, |c| c + 1);
+    c0.iter()
+        .copied()
+        .chain(c1.iter().map(|c| c + offset))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,7 +344,7 @@ mod tests {
 
     #[test]
     fn test_iter_edges() {
-        let g: Graph<Directed> = Graph(petgraph::Graph::from_edges(&[(0, 1), (0, 0)]));
+        let g: Graph<Directed> = Graph::from(petgraph::Graph::from_edges([(0, 1), (0, 0)]));
         assert_eq!(
             vec![(0, 1), (0, 0)],
             g.iter_edges()
@@ -260,14 +354,14 @@ mod tests {
 
     #[test]
     fn test_append_graph() {
-        let mut g0: Graph<Directed> = Graph(petgraph::Graph::from_edges(&[(0, 1)]));
+        let mut g0: Graph<Directed> = Graph::from(petgraph::Graph::from_edges([(0, 1)]));
         assert_eq!(2, g0.n_nodes());
         assert_eq!(
             vec![(0, 1)],
             g0.iter_edges()
                 .collect::<Vec<(NodeIndexType, NodeIndexType)>>()
         );
-        let g1 = Graph(petgraph::Graph::from_edges(&[(1, 0)]));
+        let g1 = Graph::from(petgraph::Graph::from_edges([(1, 0)]));
         g0.append_graph(&g1);
         assert_eq!(4, g0.n_nodes());
         assert_eq!(
@@ -275,7 +369,7 @@ mod tests {
             g0.iter_edges()
                 .collect::<Vec<(NodeIndexType, NodeIndexType)>>()
         );
-        let g2 = Graph(petgraph::Graph::from_edges(&[(0, 1), (1, 0)]));
+        let g2 = Graph::from(petgraph::Graph::from_edges([(0, 1), (1, 0)]));
         g0.append_graph(&g2);
         assert_eq!(6, g0.n_nodes());
         assert_eq!(
@@ -284,4 +378,34 @@ mod tests {
                 .collect::<Vec<(NodeIndexType, NodeIndexType)>>()
         );
     }
+
+    #[test]
+    fn test_append_graph_communities() {
+        let mut g0: Graph<Directed> = Graph::default();
+        let mut g1 = Graph::from(petgraph::Graph::from_edges([(0, 1), (1, 2)]));
+        g1.set_communities(vec![0, 0, 1]);
+        g0.append_graph(&g1);
+        assert_eq!(Some([0, 0, 1].as_slice()), g0.communities());
+        g0.append_graph(&g1);
+        assert_eq!(Some([0, 0, 1, 2, 2, 3].as_slice()), g0.communities());
+        g0.append_graph(&Graph::from(petgraph::Graph::from_edges([(0, 1)])));
+        assert_eq!(None, g0.communities());
+        g0.append_graph(&g1);
+        assert_eq!(None, g0.communities());
+    }
+
+    #[test]
+    fn test_append_graph_positions() {
+        let mut g0: Graph<Directed> = Graph::default();
+        let mut g1 = Graph::from(petgraph::Graph::from_edges([(0, 1)]));
+        g1.set_positions(vec![vec![0., 0.], vec![1., 0.]]);
+        g0.append_graph(&g1);
+        g0.append_graph(&g1);
+        assert_eq!(
+            Some([vec![0., 0.], vec![1., 0.], vec![0., 0.], vec![1., 0.]].as_slice()),
+            g0.positions()
+        );
+        g0.new_node();
+        assert_eq!(None, g0.positions());
+    }
 }
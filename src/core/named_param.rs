@@ -44,9 +44,22 @@ pub trait NamedParam<T> {
     /// In case this objects expect no parameter, this function must return an empty vector.
     fn expected_parameter_types(&self) -> Vec<ParameterType>;
 
+    /// Returns the names, the types and the default values of the optional parameters.
+    ///
+    /// Optional parameters are given after the expected ones, as `name=value` couples (eg. `foo/1,2,bar=3`).
+    /// Their values are appended to the ones of the expected parameters before the call to `try_with_params`, in the order they are returned by this function.
+    /// The default value of an optional parameter is used when it is not given.
+    /// Parameters of type [`GeneratorSpec`](ParameterType::GeneratorSpec) may contain commas (eg. `foo/1,2,bar=baz/3,4`), and thus take all the parameters that follow them.
+    ///
+    /// By default, this function returns an empty vector.
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        vec![]
+    }
+
     /// Tries to build an instance of the related alternative given the parameters.
     ///
     /// The parameter must be computed from the expected types returned by `expected_parameter_types` and a string that concatenate the parameters values split by commas.
+    /// The values of the optional parameters (see `optional_parameters`) follow the ones of the expected parameters.
     fn try_with_params(&self, parameter_values: Vec<ParameterValue>) -> Result<T>;
 }
 
@@ -61,7 +74,8 @@ where
     };
     for named_factory in collection.iter() {
         if named_factory.name() == kind {
-            let parameter_parser = ParameterParser::new(named_factory.expected_parameter_types());
+            let parameter_parser = ParameterParser::new(named_factory.expected_parameter_types())
+                .with_optional_parameters(named_factory.optional_parameters());
             let parameter_values = parameter_parser
                 .parse(str_params)
                 .with_context(|| {
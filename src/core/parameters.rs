@@ -2,11 +2,23 @@ use anyhow::{anyhow, Context, Result};
 
 pub(crate) struct ParameterParser {
     parameter_types: Vec<ParameterType>,
+    optional_parameters: Vec<(&'static str, ParameterType, ParameterValue)>,
 }
 
 impl ParameterParser {
     pub fn new(parameter_types: Vec<ParameterType>) -> Self {
-        Self { parameter_types }
+        Self {
+            parameter_types,
+            optional_parameters: vec![],
+        }
+    }
+
+    pub fn with_optional_parameters(
+        mut self,
+        optional_parameters: Vec<(&'static str, ParameterType, ParameterValue)>,
+    ) -> Self {
+        self.optional_parameters = optional_parameters;
+        self
     }
 
     pub fn parse(&self, str_params: &str) -> Result<Vec<ParameterValue>> {
@@ -15,16 +27,60 @@ impl ParameterParser {
         } else {
             str_params.split(',').collect::<Vec<&str>>()
         };
-        if parameters.len() != self.parameter_types.len() {
+        let n_mandatory = parameters
+            .iter()
+            .position(|p| self.optional_parameter_index(p).is_some())
+            .unwrap_or(parameters.len());
+        if n_mandatory != self.parameter_types.len() {
             return Err(anyhow!(
                 "expected {} parameters, got {}",
                 self.parameter_types.len(),
-                parameters.len()
+                n_mandatory
             ));
         }
-        (0..parameters.len())
+        let mut values = (0..n_mandatory)
             .map(|i| self.parameter_types[i].parse(parameters[i]))
-            .collect()
+            .collect::<Result<Vec<ParameterValue>>>()?;
+        let mut str_optional_values: Vec<(usize, String)> = vec![];
+        for (i, p) in parameters.iter().enumerate().skip(n_mandatory) {
+            let index = self.optional_parameter_index(p).ok_or_else(|| {
+                anyhow!(r#"unexpected parameter "{}" after the optional ones"#, p)
+            })?;
+            let (name, parameter_type, _) = &self.optional_parameters[index];
+            if let ParameterType::GeneratorSpec = parameter_type {
+                // generator specifications may contain commas, so they take the remaining parameters
+                let str_value = parameters[i..].join(",")[name.len() + 1..].to_string();
+                str_optional_values.push((index, str_value));
+                break;
+            }
+            str_optional_values.push((index, p[name.len() + 1..].to_string()));
+        }
+        let mut optional_values: Vec<Option<ParameterValue>> =
+            vec![None; self.optional_parameters.len()];
+        for (index, str_value) in str_optional_values {
+            let (name, parameter_type, _) = &self.optional_parameters[index];
+            if optional_values[index].is_some() {
+                return Err(anyhow!(r#"optional parameter "{}" is set twice"#, name));
+            }
+            let value = parameter_type
+                .parse(&str_value)
+                .with_context(|| format!(r#"while evaluating optional parameter "{}""#, name))?;
+            optional_values[index] = Some(value);
+        }
+        values.extend(
+            optional_values
+                .into_iter()
+                .zip(self.optional_parameters.iter())
+                .map(|(v, (_, _, default_value))| v.unwrap_or_else(|| default_value.clone())),
+        );
+        Ok(values)
+    }
+
+    fn optional_parameter_index(&self, str_param: &str) -> Option<usize> {
+        let (name, _) = str_param.split_once('=')?;
+        self.optional_parameters
+            .iter()
+            .position(|(n, _, _)| *n == name)
     }
 }
 
@@ -34,6 +90,16 @@ pub enum ParameterType {
     PositiveInteger,
     /// A floating point number between 0 and 1 (both allowed)
     Probability,
+    /// A positive floating point number, possibly null
+    PositiveFloat,
+    /// A Boolean value, written `true` or `false`
+    Boolean,
+    /// A string of characters
+    String,
+    /// A generator specification (eg. `er/10,0.5`), read as a string of characters
+    ///
+    /// Since generator specifications may contain commas, optional parameters of this type take all the parameters that follow them.
+    GeneratorSpec,
 }
 
 impl ParameterType {
@@ -52,6 +118,22 @@ impl ParameterType {
                     ParameterValue::Probability(p)
                 }
             }
+            ParameterType::PositiveFloat => {
+                let context = "while translating a string into a positive floating point number";
+                let f: f64 = str::parse(param).context(context)?;
+                if !f.is_finite() || f < 0. {
+                    return Err(anyhow!("value must be a finite positive number")).context(context);
+                } else {
+                    ParameterValue::PositiveFloat(f)
+                }
+            }
+            ParameterType::Boolean => ParameterValue::Boolean(
+                str::parse::<bool>(param)
+                    .context("while translating a string into a Boolean value")?,
+            ),
+            ParameterType::String | ParameterType::GeneratorSpec => {
+                ParameterValue::String(param.to_string())
+            }
         })
     }
 }
@@ -60,12 +142,18 @@ impl ParameterType {
 ///
 /// Its value should have been checked against an unexpected type.
 /// The value must be unwrapped using a compatible function.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum ParameterValue {
     /// A positive integer, possibly null
     PositiveInteger(usize),
     /// A floating point number between 0 and 1 (both allowed)
     Probability(f64),
+    /// A positive floating point number, possibly null
+    PositiveFloat(f64),
+    /// A Boolean value
+    Boolean(bool),
+    /// A string of characters
+    String(String),
 }
 
 impl ParameterValue {
@@ -81,14 +169,38 @@ impl ParameterValue {
         }
     }
 
-    /// Unwraps a parameter value which value can be seen as a probability.
+    /// Unwraps a parameter value which value can be seen as a floating point number (probabilities included).
     ///
     /// # Panics
     ///
-    /// This function panics if the value can not be seen as a probability.
+    /// This function panics if the value can not be seen as a floating point number.
     pub fn unwrap_f64(&self) -> f64 {
         match self {
-            ParameterValue::Probability(f) => *f,
+            ParameterValue::Probability(f) | ParameterValue::PositiveFloat(f) => *f,
+            _ => panic!(),
+        }
+    }
+
+    /// Unwraps a parameter value which value can be seen as a Boolean.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the value can not be seen as a Boolean.
+    pub fn unwrap_bool(&self) -> bool {
+        match self {
+            ParameterValue::Boolean(b) => *b,
+            _ => panic!(),
+        }
+    }
+
+    /// Unwraps a parameter value which value can be seen as a string.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the value can not be seen as a string.
+    pub fn unwrap_str(&self) -> &str {
+        match self {
+            ParameterValue::String(s) => s,
             _ => panic!(),
         }
     }
@@ -162,4 +274,140 @@ mod tests {
         assert!(parser.parse("1.5").is_err());
         assert!(parser.parse("a").is_err());
     }
+
+    #[test]
+    pub fn test_positive_float_ok() {
+        let parser = ParameterParser::new(vec![ParameterType::PositiveFloat]);
+        assert_eq!(
+            vec![ParameterValue::PositiveFloat(2.5)],
+            parser.parse("2.5").unwrap()
+        );
+        assert_eq!(
+            vec![ParameterValue::PositiveFloat(0.)],
+            parser.parse("0").unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_positive_float_not_ok() {
+        let parser = ParameterParser::new(vec![ParameterType::PositiveFloat]);
+        assert!(parser.parse("-1.5").is_err());
+        assert!(parser.parse("inf").is_err());
+        assert!(parser.parse("a").is_err());
+    }
+
+    #[test]
+    pub fn test_boolean() {
+        let parser = ParameterParser::new(vec![ParameterType::Boolean]);
+        assert_eq!(
+            vec![ParameterValue::Boolean(true)],
+            parser.parse("true").unwrap()
+        );
+        assert!(parser.parse("1").is_err());
+    }
+
+    #[test]
+    pub fn test_optional_default() {
+        let parser = ParameterParser::new(vec![ParameterType::PositiveInteger])
+            .with_optional_parameters(vec![(
+                "opt",
+                ParameterType::Boolean,
+                ParameterValue::Boolean(false),
+            )]);
+        assert_eq!(
+            vec![
+                ParameterValue::PositiveInteger(1),
+                ParameterValue::Boolean(false)
+            ],
+            parser.parse("1").unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_optional_set() {
+        let parser = ParameterParser::new(vec![ParameterType::PositiveInteger])
+            .with_optional_parameters(vec![
+                ("a", ParameterType::Boolean, ParameterValue::Boolean(false)),
+                (
+                    "b",
+                    ParameterType::String,
+                    ParameterValue::String("".to_string()),
+                ),
+            ]);
+        assert_eq!(
+            vec![
+                ParameterValue::PositiveInteger(1),
+                ParameterValue::Boolean(false),
+                ParameterValue::String("x=y".to_string())
+            ],
+            parser.parse("1,b=x=y").unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_optional_generator_spec() {
+        let parser = ParameterParser::new(vec![ParameterType::PositiveInteger])
+            .with_optional_parameters(vec![
+                ("a", ParameterType::Boolean, ParameterValue::Boolean(false)),
+                (
+                    "seed",
+                    ParameterType::GeneratorSpec,
+                    ParameterValue::String("".to_string()),
+                ),
+            ]);
+        assert_eq!(
+            vec![
+                ParameterValue::PositiveInteger(1),
+                ParameterValue::Boolean(true),
+                ParameterValue::String("er/10,0.5".to_string())
+            ],
+            parser.parse("1,a=true,seed=er/10,0.5").unwrap()
+        );
+        assert_eq!(
+            vec![
+                ParameterValue::PositiveInteger(1),
+                ParameterValue::Boolean(false),
+                ParameterValue::String("ba/10,2,seed=star/3,a=true".to_string())
+            ],
+            parser.parse("1,seed=ba/10,2,seed=star/3,a=true").unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_optional_string_without_commas() {
+        let parser = ParameterParser::new(vec![ParameterType::PositiveInteger])
+            .with_optional_parameters(vec![
+                (
+                    "a",
+                    ParameterType::String,
+                    ParameterValue::String("".to_string()),
+                ),
+                ("b", ParameterType::Boolean, ParameterValue::Boolean(false)),
+            ]);
+        assert_eq!(
+            vec![
+                ParameterValue::PositiveInteger(1),
+                ParameterValue::String("x".to_string()),
+                ParameterValue::Boolean(true)
+            ],
+            parser.parse("1,a=x,b=true").unwrap()
+        );
+        let error = parser.parse("1,a=x,foo=1").unwrap_err();
+        assert!(error.to_string().contains("unexpected parameter"));
+    }
+
+    #[test]
+    pub fn test_optional_not_ok() {
+        let parser = ParameterParser::new(vec![ParameterType::PositiveInteger])
+            .with_optional_parameters(vec![(
+                "opt",
+                ParameterType::Boolean,
+                ParameterValue::Boolean(false),
+            )]);
+        assert!(parser.parse("opt=true").is_err());
+        assert!(parser.parse("1,opt=true,2").is_err());
+        assert!(parser.parse("1,opt=true,opt=false").is_err());
+        assert!(parser.parse("1,opt=1").is_err());
+        assert!(parser.parse("1,foo=true").is_err());
+    }
 }
@@ -1,8 +1,9 @@
 use super::{BoxedDisplay, GraphDisplay};
-use crate::{NamedParam, ParameterType, ParameterValue};
+use crate::{NamedParam, NodeIndexType, ParameterType, ParameterValue};
 use anyhow::Result;
 use petgraph::{
     dot::{Config, Dot},
+    graph::NodeIndex,
     EdgeType,
 };
 
@@ -18,7 +19,11 @@ where
     }
 
     fn description(&self) -> Vec<&'static str> {
-        vec!["Output a graph using the Graphviz DOT format."]
+        vec![
+            "Output a graph using the Graphviz DOT format.",
+            "Node communities are given by the \"community\" attribute, if any.",
+            "Node positions are given by the \"pos\" attribute, if any.",
+        ]
     }
 
     fn expected_parameter_types(&self) -> Vec<ParameterType> {
@@ -27,8 +32,27 @@ where
 
     fn try_with_params(&self, _parameter_values: Vec<ParameterValue>) -> Result<BoxedDisplay<Ty>> {
         Ok(Box::new(|f, g| {
-            let dot_display =
-                Dot::with_config(g.petgraph(), &[Config::NodeIndexLabel, Config::EdgeNoLabel]);
+            let get_node_attributes = |_, (n, _): (NodeIndex<NodeIndexType>, &())| {
+                let mut attributes = String::new();
+                if let Some(communities) = g.communities() {
+                    attributes.push_str(&format!("community = {} ", communities[n.index()]));
+                }
+                if let Some(positions) = g.positions() {
+                    let coordinates = positions[n.index()]
+                        .iter()
+                        .map(|x| x.to_string())
+                        .collect::<Vec<String>>()
+                        .join(",");
+                    attributes.push_str(&format!("pos = \"{}\" ", coordinates));
+                }
+                attributes
+            };
+            let dot_display = Dot::with_attr_getters(
+                g.petgraph(),
+                &[Config::NodeIndexLabel, Config::EdgeNoLabel],
+                &|_, _| String::new(),
+                &get_node_attributes,
+            );
             std::fmt::Debug::fmt(&dot_display, f)
         }))
     }
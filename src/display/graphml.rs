@@ -1,9 +1,9 @@
 use super::{BoxedDisplay, GraphDisplay};
-use crate::{NamedParam, ParameterType, ParameterValue};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
 use anyhow::Result;
 use petgraph::EdgeType;
 use petgraph_graphml::GraphMl;
-use std::fmt::Display;
+use std::{borrow::Cow, fmt::Display};
 
 #[derive(Default)]
 pub struct GraphMLGraphDisplay;
@@ -17,7 +17,11 @@ where
     }
 
     fn description(&self) -> Vec<&'static str> {
-        vec!["Output a graph using the GraphML."]
+        vec![
+            "Output a graph using the GraphML.",
+            "Node communities are given by the \"community\" attribute, if any.",
+            "Node positions are given by the \"x\", \"y\" and \"z\" attributes (then \"x3\", \"x4\", ...), if any.",
+        ]
     }
 
     fn expected_parameter_types(&self) -> Vec<ParameterType> {
@@ -26,10 +30,46 @@ where
 
     fn try_with_params(&self, _parameter_values: Vec<ParameterValue>) -> Result<BoxedDisplay<Ty>> {
         Ok(Box::new(|f, g| {
-            let graphml = GraphMl::new(g.petgraph()).pretty_print(true);
+            if g.communities().is_none() && g.positions().is_none() {
+                let graphml = GraphMl::new(g.petgraph()).pretty_print(true);
+                return graphml.fmt(f);
+            }
+            let with_attributes = g
+                .petgraph()
+                .map(|n, _| node_attributes(g, n.index()), |_, _| ());
+            let graphml = GraphMl::new(&with_attributes)
+                .pretty_print(true)
+                .export_node_weights(Box::new(|attributes| {
+                    attributes
+                        .iter()
+                        .map(|(k, v)| (k.clone(), v.into()))
+                        .collect()
+                }));
             graphml.fmt(f)
         }))
     }
 }
 
 impl<Ty> GraphDisplay<Ty> for GraphMLGraphDisplay where Ty: EdgeType {}
+
+fn node_attributes<Ty>(g: &Graph<Ty>, node: usize) -> Vec<(Cow<'static, str>, String)>
+where
+    Ty: EdgeType,
+{
+    let mut attributes = vec![];
+    if let Some(communities) = g.communities() {
+        attributes.push(("community".into(), communities[node].to_string()));
+    }
+    if let Some(positions) = g.positions() {
+        positions[node].iter().enumerate().for_each(|(i, x)| {
+            let name = match i {
+                0 => "x".into(),
+                1 => "y".into(),
+                2 => "z".into(),
+                _ => format!("x{}", i).into(),
+            };
+            attributes.push((name, x.to_string()));
+        });
+    }
+    attributes
+}
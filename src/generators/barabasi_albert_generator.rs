@@ -1,8 +1,10 @@
 use super::{BoxedGenerator, GeneratorFactory};
-use crate::{NamedParam, ParameterType, ParameterValue};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
 use anyhow::{anyhow, Context, Result};
-use petgraph::EdgeType;
+use petgraph::{Directed, EdgeType, Undirected};
 use rand::Rng;
+use rand_core::SeedableRng;
+use rand_pcg::Pcg32;
 
 /// A factory used to build generators for [Barabási-Albert](https://en.wikipedia.org/wiki/Barab%C3%A1si%E2%80%93Albert_model) graphs.
 ///
@@ -10,9 +12,17 @@ use rand::Rng;
 ///
 /// Such factories can be created by passing `ba/n,m` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
 ///   - `n` is the size of graph to produce;
-///   - `m` is the size of the graph used for the initialization step.
+///   - `m` is the number of edges brought by each new node.
+///
+/// By default, graphs used for initialization are star graphs of `m` nodes.
+/// The optional parameter `seed` (eg. `ba/1000,3,seed=er/10,0.5`) replaces them by the graphs produced by another generator, given by its specification.
+/// Since this specification may contain commas, `seed` must be given after the other optional parameters.
+/// In this case, new nodes are linked to as many existing nodes as possible when the seed graph has less than `m` nodes,
+/// and nodes are chosen uniformly while the seed graph has no edge.
+/// Seed graphs must have less than `n` nodes: building the generator fails if a first seed graph drawn for the check is too large,
+/// and generating a graph panics if a seed graph is too large (which may only happen for seed generators producing graphs of random sizes).
+/// The communities and the positions of the seed graph are kept; each new node gets the ones of the first node it is linked to.
 ///
-/// Graphs used for initialization are star graphs.
 /// Both parameters must be higher than zero, and `n` must be higher than `m`.
 #[derive(Default)]
 pub struct BarabasiAlbertGeneratorFactory;
@@ -29,7 +39,8 @@ where
     fn description(&self) -> Vec<&'static str> {
         vec![
             "A generator following the Barabási-Albert model, initialized by a star graph.",
-            "First parameter gives the number of nodes of the graph, while the second one gives the number of nodes of the initial star graph."
+            "First parameter gives the number of nodes of the graph, while the second one gives the number of edges brought by each new node, which is also the number of nodes of the initial star graph when \"seed\" is not set.",
+            "The initial graph can be replaced by the one produced by the generator given by the optional parameter \"seed\".",
         ]
     }
 
@@ -40,6 +51,14 @@ where
         ]
     }
 
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        vec![(
+            "seed",
+            ParameterType::GeneratorSpec,
+            ParameterValue::String(String::new()),
+        )]
+    }
+
     fn try_with_params(
         &self,
         parameter_values: Vec<ParameterValue>,
@@ -47,14 +66,34 @@ where
         let context = "while building a Barabasi-Albert generator";
         let n = parameter_values[0].unwrap_usize();
         let m = parameter_values[1].unwrap_usize();
+        let str_seed = parameter_values[2].unwrap_str();
         if m == 0 || m >= n {
             return Err(anyhow!(
                 r#"second parameter ("m") must be higher than 0 and lower than the first one ("n")"#
             ))
             .context(context);
         }
+        if str_seed.is_empty() {
+            return Ok(Box::new(move |r| {
+                petgraph_gen::barabasi_albert_graph(r, n, m, None).into()
+            }));
+        }
+        let seed_generator = SeedGenerator::try_from_str::<Ty>(str_seed)
+            .context(r#"while evaluating optional parameter "seed""#)
+            .context(context)?;
+        if seed_generator
+            .generate::<Ty>(&mut Pcg32::seed_from_u64(0))
+            .n_nodes()
+            >= n
+        {
+            return Err(anyhow!(
+                r#"seed graphs must have less nodes than the first parameter ("n")"#
+            ))
+            .context(context);
+        }
         Ok(Box::new(move |r| {
-            petgraph_gen::barabasi_albert_graph(r, n, m, None).into()
+            let seed = seed_generator.generate(&mut Pcg32::seed_from_u64(r.gen()));
+            grow_seed(seed, n, m, r)
         }))
     }
 }
@@ -66,18 +105,153 @@ where
 {
 }
 
+/// The generator of the seed graphs, built from the registry matching the kind of graphs to produce.
+enum SeedGenerator {
+    Directed(BoxedGenerator<Directed, Pcg32>),
+    Undirected(BoxedGenerator<Undirected, Pcg32>),
+}
+
+impl SeedGenerator {
+    fn try_from_str<Ty>(s: &str) -> Result<Self>
+    where
+        Ty: EdgeType,
+    {
+        if Ty::is_directed() {
+            Ok(Self::Directed(super::directed_generator_factory_from_str(
+                s,
+            )?))
+        } else {
+            Ok(Self::Undirected(
+                super::undirected_generator_factory_from_str(s)?,
+            ))
+        }
+    }
+
+    fn generate<Ty>(&self, r: &mut Pcg32) -> Graph<Ty>
+    where
+        Ty: EdgeType,
+    {
+        match self {
+            Self::Directed(generator) => copy_graph(&generator(r)),
+            Self::Undirected(generator) => copy_graph(&generator(r)),
+        }
+    }
+}
+
+/// Copies a graph, including its communities and its positions, into a graph with the same edge type.
+fn copy_graph<Ty1, Ty2>(g: &Graph<Ty1>) -> Graph<Ty2>
+where
+    Ty1: EdgeType,
+    Ty2: EdgeType,
+{
+    let mut copy = Graph::with_capacity(g.n_nodes(), g.n_edges());
+    (0..g.n_nodes()).for_each(|_| copy.new_node());
+    g.iter_edges().for_each(|(a, b)| copy.new_edge(a, b));
+    if let Some(communities) = g.communities() {
+        copy.set_communities(communities.to_vec());
+    }
+    if let Some(positions) = g.positions() {
+        copy.set_positions(positions.to_vec());
+    }
+    copy
+}
+
+/// Adds nodes to a seed graph until it has `n` nodes, following the Barabási-Albert model.
+///
+/// # Panics
+///
+/// Panics if the seed graph does not have less than `n` nodes.
+fn grow_seed<Ty, R>(mut g: Graph<Ty>, n: usize, m: usize, r: &mut R) -> Graph<Ty>
+where
+    Ty: EdgeType,
+    R: Rng,
+{
+    assert!(
+        g.n_nodes() < n,
+        "Barabási-Albert seed graph has {} nodes, but it must have less than {}",
+        g.n_nodes(),
+        n
+    );
+    let mut repeated_nodes = Vec::with_capacity(2 * (g.n_edges() + (n - g.n_nodes()) * m));
+    let mut linked = vec![false; n];
+    for (a, b) in g.iter_edges() {
+        repeated_nodes.push(a);
+        repeated_nodes.push(b);
+        linked[a] = true;
+        linked[b] = true;
+    }
+    let mut n_linked = linked.iter().filter(|l| **l).count();
+    let mut communities = g.communities().map(|c| c.to_vec());
+    let mut positions = g.positions().map(|p| p.to_vec());
+    let mut picked = vec![false; n];
+    let mut targets = Vec::with_capacity(m);
+    for new_node in g.n_nodes()..n {
+        g.new_node();
+        while targets.len() < m.min(n_linked) {
+            let target = repeated_nodes[r.gen_range(0..repeated_nodes.len())];
+            if !picked[target] {
+                picked[target] = true;
+                targets.push(target);
+            }
+        }
+        while targets.len() < m.min(new_node) {
+            let target = r.gen_range(0..new_node);
+            if !picked[target] {
+                picked[target] = true;
+                targets.push(target);
+            }
+        }
+        match targets.first() {
+            Some(first) => {
+                if let Some(c) = communities.as_mut() {
+                    c.push(c[*first]);
+                }
+                if let Some(p) = positions.as_mut() {
+                    p.push(p[*first].clone());
+                }
+            }
+            None => {
+                communities = None;
+                positions = None;
+            }
+        }
+        for target in targets.drain(..) {
+            g.new_edge(new_node, target);
+            repeated_nodes.push(new_node);
+            repeated_nodes.push(target);
+            picked[target] = false;
+            if !linked[target] {
+                linked[target] = true;
+                n_linked += 1;
+            }
+        }
+        if !linked[new_node] && new_node > 0 {
+            linked[new_node] = true;
+            n_linked += 1;
+        }
+    }
+    if let Some(c) = communities {
+        g.set_communities(c);
+    }
+    if let Some(p) = positions {
+        g.set_positions(p);
+    }
+    g
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{Graph, NodeIndexType};
-    use petgraph::Directed;
+    use petgraph::{Directed, Undirected};
     use rand::rngs::ThreadRng;
 
     #[test]
     fn test_m_is_zero() {
         assert!((BarabasiAlbertGeneratorFactory.try_with_params(vec![
             ParameterValue::PositiveInteger(2),
-            ParameterValue::PositiveInteger(0)
+            ParameterValue::PositiveInteger(0),
+            ParameterValue::String(String::new()),
         ]) as Result<BoxedGenerator<Directed, ThreadRng>>)
             .is_err())
     }
@@ -86,7 +260,8 @@ mod tests {
     fn test_n_is_not_higher_than_m() {
         assert!((BarabasiAlbertGeneratorFactory.try_with_params(vec![
             ParameterValue::PositiveInteger(2),
-            ParameterValue::PositiveInteger(2)
+            ParameterValue::PositiveInteger(2),
+            ParameterValue::String(String::new()),
         ]) as Result<BoxedGenerator<Directed, ThreadRng>>)
             .is_err())
     }
@@ -98,6 +273,7 @@ mod tests {
             .try_with_params(vec![
                 ParameterValue::PositiveInteger(4),
                 ParameterValue::PositiveInteger(3),
+                ParameterValue::String(String::new()),
             ])
             .unwrap()(&mut rng);
         assert_eq!(
@@ -106,4 +282,119 @@ mod tests {
                 .collect::<Vec<(NodeIndexType, NodeIndexType)>>()
         );
     }
+
+    fn seed_params(n: usize, m: usize, seed: &str) -> Vec<ParameterValue> {
+        vec![
+            ParameterValue::PositiveInteger(n),
+            ParameterValue::PositiveInteger(m),
+            ParameterValue::String(seed.to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_unknown_seed() {
+        assert!(
+            (BarabasiAlbertGeneratorFactory.try_with_params(seed_params(10, 2, "foo/3"))
+                as Result<BoxedGenerator<Directed, ThreadRng>>)
+                .is_err()
+        );
+        assert!(
+            (BarabasiAlbertGeneratorFactory.try_with_params(seed_params(10, 2, "tournament/3"))
+                as Result<BoxedGenerator<Undirected, ThreadRng>>)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_nested_seeds() {
+        let mut rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let generator = crate::generators::undirected_generator_factory_from_str(
+            "ba/100,2,seed=ba/10,2,seed=star/3",
+        )
+        .unwrap();
+        let g = generator(&mut rng);
+        assert_eq!(100, g.n_nodes());
+        assert_eq!(2 + 7 * 2 + 90 * 2, g.n_edges());
+        assert!(crate::generators::undirected_generator_factory_from_str(
+            "ba/100,2,seed=er/10,0.5,foo=1"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_seed_metadata() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = BarabasiAlbertGeneratorFactory
+            .try_with_params(seed_params(50, 1, "sbm/2,5,1,0"))
+            .unwrap()(&mut rng);
+        let communities = g.communities().unwrap();
+        assert_eq!(50, communities.len());
+        assert!(g
+            .iter_edges()
+            .all(|(a, b)| communities[a] == communities[b]));
+        let g: Graph<Undirected> = BarabasiAlbertGeneratorFactory
+            .try_with_params(seed_params(50, 2, "rgg/10,0.5"))
+            .unwrap()(&mut rng);
+        assert_eq!(50, g.positions().unwrap().len());
+    }
+
+    #[test]
+    fn test_complete_seed() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = BarabasiAlbertGeneratorFactory
+            .try_with_params(seed_params(100, 2, "complete/5"))
+            .unwrap()(&mut rng);
+        assert_eq!(100, g.n_nodes());
+        assert_eq!(10 + 95 * 2, g.n_edges());
+        let edges = g
+            .iter_edges()
+            .collect::<Vec<(NodeIndexType, NodeIndexType)>>();
+        assert_eq!(
+            (0..5)
+                .flat_map(|i| (i + 1..5).map(move |j| (i, j)))
+                .collect::<Vec<(NodeIndexType, NodeIndexType)>>(),
+            edges[..10]
+        );
+        assert!(edges[10..].iter().all(|(a, b)| a > b));
+    }
+
+    #[test]
+    fn test_small_seed_without_edges() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = BarabasiAlbertGeneratorFactory
+            .try_with_params(seed_params(10, 3, "er/2,0"))
+            .unwrap()(&mut rng);
+        assert_eq!(10, g.n_nodes());
+        assert_eq!(2 + 3 * 7, g.n_edges());
+        let mut edges = g
+            .iter_edges()
+            .collect::<Vec<(NodeIndexType, NodeIndexType)>>();
+        edges.sort_unstable();
+        edges.dedup();
+        assert_eq!(g.n_edges(), edges.len());
+    }
+
+    #[test]
+    fn test_large_seed() {
+        assert!(
+            (BarabasiAlbertGeneratorFactory.try_with_params(seed_params(5, 2, "path/8"))
+                as Result<BoxedGenerator<Directed, ThreadRng>>)
+                .is_err()
+        );
+        assert!(
+            (BarabasiAlbertGeneratorFactory.try_with_params(seed_params(5, 2, "path/5"))
+                as Result<BoxedGenerator<Directed, ThreadRng>>)
+                .is_err()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_grow_large_seed() {
+        let mut rng = rand::thread_rng();
+        let seed: Graph<Directed> = crate::generators::PathGeneratorFactory
+            .try_with_params(vec![ParameterValue::PositiveInteger(8)])
+            .unwrap()(&mut rng);
+        grow_seed(seed, 5, 2, &mut rng);
+    }
 }
@@ -0,0 +1,131 @@
+use super::{complete_generator, BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::EdgeType;
+use rand::Rng;
+
+/// A factory used to build generators for [barbell graphs](https://en.wikipedia.org/wiki/Barbell_graph).
+///
+/// A barbell graph is made of two complete graphs linked by a path.
+/// The first complete graph is made of the nodes with the lowest labels, followed by the inner nodes of the path, and then by the second complete graph.
+///
+/// In directed graphs generated by this objects, complete graphs have edges in both directions, and the path goes from the first complete graph to the second one.
+///
+/// Such factories can be created by passing `barbell/m,p` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `m` is the number of nodes of each complete graph;
+///   - `p` is the number of nodes in the path between the complete graphs (excluding the ones of the complete graphs).
+///
+/// Parameter `m` must be at least 2.
+#[derive(Default)]
+pub struct BarbellGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for BarbellGeneratorFactory
+where
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "barbell"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a barbell graph (two complete graphs linked by a path).",
+            "First parameter gives the number of nodes of each complete graph, while the second one gives the number of nodes of the path between them.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a barbell generator";
+        let m = parameter_values[0].unwrap_usize();
+        let p = parameter_values[1].unwrap_usize();
+        if m < 2 {
+            return Err(anyhow!(r#"first parameter ("m") must be at least 2"#)).context(context);
+        }
+        Ok(Box::new(move |_| {
+            let n = 2 * m + p;
+            let mut g = Graph::with_capacity(n, m * (m - 1) * 2 + p + 1);
+            (0..n).for_each(|_| g.new_node());
+            complete_generator::add_clique(&mut g, 0..m);
+            (m - 1..m + p).for_each(|i| g.new_edge(i, i + 1));
+            complete_generator::add_clique(&mut g, m + p..n);
+            g
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for BarbellGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+    use petgraph::Undirected;
+    use rand::rngs::ThreadRng;
+
+    #[test]
+    fn test_m_is_lower_than_2() {
+        assert!((BarbellGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(1),
+            ParameterValue::PositiveInteger(2)
+        ]) as Result<BoxedGenerator<Undirected, ThreadRng>>)
+            .is_err())
+    }
+
+    #[test]
+    fn test_barbell_no_path() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = BarbellGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::PositiveInteger(0),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(4, g.n_nodes());
+        assert_eq!(
+            vec![(0, 1), (1, 2), (2, 3)],
+            g.iter_edges()
+                .collect::<Vec<(NodeIndexType, NodeIndexType)>>()
+        );
+    }
+
+    #[test]
+    fn test_barbell() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = BarbellGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::PositiveInteger(1),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(7, g.n_nodes());
+        assert_eq!(
+            vec![
+                (0, 1),
+                (0, 2),
+                (1, 2),
+                (2, 3),
+                (3, 4),
+                (4, 5),
+                (4, 6),
+                (5, 6)
+            ],
+            g.iter_edges()
+                .collect::<Vec<(NodeIndexType, NodeIndexType)>>()
+        );
+    }
+}
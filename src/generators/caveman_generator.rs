@@ -0,0 +1,386 @@
+use super::{complete_generator, BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::EdgeType;
+use rand::{
+    distributions::{Distribution, Uniform},
+    Rng,
+};
+use std::collections::HashSet;
+
+/// A factory used to build generators for [connected caveman graphs](https://mathworld.wolfram.com/CavemanGraph.html).
+///
+/// A connected caveman graph is made of `l` complete graphs of `k` nodes (the "caves"), the i-th one being made of the nodes with labels from `i*k` to `(i+1)*k-1`.
+/// In each cave, the edge between the two first nodes is removed, and the first node is linked to the last node of the previous cave instead, so that the caves form a ring.
+///
+/// The cave of each node is available through [`Graph::communities`].
+///
+/// In directed graphs generated by this objects, edges inside the caves are set in both directions, and the edges between the caves go from a cave to the next one.
+///
+/// Such factories can be created by passing `caveman/l,k` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `l` is the number of caves;
+///   - `k` is the number of nodes in each cave.
+///
+/// Parameter `l` must be at least 2, and `k` must be at least 3 (caves of two nodes would have no internal edge left).
+#[derive(Default)]
+pub struct ConnectedCavemanGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for ConnectedCavemanGeneratorFactory
+where
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "caveman"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a connected caveman graph (a ring of complete graphs, each one missing an edge).",
+            "First parameter gives the number of complete graphs, while the second one gives their number of nodes.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a connected caveman generator";
+        let (l, k) = check_cliques(&parameter_values, 2, 3).context(context)?;
+        Ok(Box::new(move |_| {
+            let n = l * k;
+            let mut g = Graph::with_capacity(n, 0);
+            (0..n).for_each(|_| g.new_node());
+            for start in (0..n).step_by(k) {
+                for i in start..start + k {
+                    for j in i + 1..start + k {
+                        if (i, j) != (start, start + 1) {
+                            g.new_edge(i, j);
+                            if Ty::is_directed() {
+                                g.new_edge(j, i);
+                            }
+                        }
+                    }
+                }
+                g.new_edge((start + n - 1) % n, start);
+            }
+            g.set_communities((0..n).map(|i| i / k).collect());
+            g
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for ConnectedCavemanGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// A factory used to build generators for relaxed caveman graphs.
+///
+/// The graph is initialized with `l` disjoint complete graphs of `k` nodes, the i-th one being made of the nodes with labels from `i*k` to `(i+1)*k-1`.
+/// Then, each edge `(u,v)` is rewired with probability `p` to `(u,w)`, where `w` is a node chosen uniformly at random;
+/// the edge is kept unchanged if `w` is equal to `u` or if `u` and `w` are already linked.
+///
+/// The complete graph each node initially belongs to is available through [`Graph::communities`].
+///
+/// In directed graphs generated by this objects, the complete graphs have edges in both directions, and each edge is considered for rewiring independently.
+///
+/// Such factories can be created by passing `relaxed_caveman/l,k,p` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `l` is the number of complete graphs;
+///   - `k` is the number of nodes in each complete graph;
+///   - `p` is the probability each edge is rewired.
+#[derive(Default)]
+pub struct RelaxedCavemanGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for RelaxedCavemanGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "relaxed_caveman"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a relaxed caveman graph (complete graphs which edges are randomly rewired).",
+            "First parameter gives the number of complete graphs, the second one gives their number of nodes, and the third one gives the rewiring probability.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+            ParameterType::Probability,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let l = parameter_values[0].unwrap_usize();
+        let k = parameter_values[1].unwrap_usize();
+        let p = parameter_values[2].unwrap_f64();
+        Ok(Box::new(move |r| build_relaxed_caveman(l, k, p, r)))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for RelaxedCavemanGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+fn build_relaxed_caveman<Ty, R>(l: usize, k: usize, p: f64, r: &mut R) -> Graph<Ty>
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    let n = l * k;
+    let key = |from: usize, to: usize| {
+        if Ty::is_directed() {
+            (from, to)
+        } else {
+            (usize::min(from, to), usize::max(from, to))
+        }
+    };
+    let mut edges = vec![];
+    for start in (0..n).step_by(usize::max(k, 1)) {
+        for i in start..start + k {
+            for j in i + 1..start + k {
+                edges.push((i, j));
+                if Ty::is_directed() {
+                    edges.push((j, i));
+                }
+            }
+        }
+    }
+    let mut existing = edges
+        .iter()
+        .map(|(from, to)| key(*from, *to))
+        .collect::<HashSet<(usize, usize)>>();
+    let proba_uniform = Uniform::new_inclusive(0., 1.);
+    for (from, to) in edges.iter_mut() {
+        if proba_uniform.sample(r) < p {
+            let new_to = r.gen_range(0..n);
+            if new_to != *from && existing.insert(key(*from, new_to)) {
+                existing.remove(&key(*from, *to));
+                *to = new_to;
+            }
+        }
+    }
+    let mut g = Graph::with_capacity(n, edges.len());
+    (0..n).for_each(|_| g.new_node());
+    edges
+        .into_iter()
+        .for_each(|(from, to)| g.new_edge(from, to));
+    g.set_communities((0..n).map(|i| i / k).collect());
+    g
+}
+
+/// A factory used to build generators for rings of cliques.
+///
+/// A ring of cliques is made of `l` complete graphs of `k` nodes, the i-th one being made of the nodes with labels from `i*k` to `(i+1)*k-1`.
+/// The second node of each complete graph is linked to the first node of the next one, so that the complete graphs form a ring.
+///
+/// The complete graph each node belongs to is available through [`Graph::communities`].
+///
+/// In directed graphs generated by this objects, the complete graphs have edges in both directions, and the edges between them go from a complete graph to the next one.
+///
+/// Such factories can be created by passing `ring_cliques/l,k` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `l` is the number of complete graphs;
+///   - `k` is the number of nodes in each complete graph.
+///
+/// Both parameters must be at least 2.
+#[derive(Default)]
+pub struct RingOfCliquesGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for RingOfCliquesGeneratorFactory
+where
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "ring_cliques"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a ring of complete graphs.",
+            "First parameter gives the number of complete graphs, while the second one gives their number of nodes.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a ring of cliques generator";
+        let (l, k) = check_cliques(&parameter_values, 2, 2).context(context)?;
+        Ok(Box::new(move |_| {
+            let n = l * k;
+            let mut g = Graph::with_capacity(n, 0);
+            (0..n).for_each(|_| g.new_node());
+            for start in (0..n).step_by(k) {
+                complete_generator::add_clique(&mut g, start..start + k);
+                g.new_edge(start + 1, (start + k) % n);
+            }
+            g.set_communities((0..n).map(|i| i / k).collect());
+            g
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for RingOfCliquesGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// Reads the number of cliques and their size from the two first parameters, checking they are both at least `min`.
+fn check_cliques(
+    parameter_values: &[ParameterValue],
+    min_l: usize,
+    min_k: usize,
+) -> Result<(usize, usize)> {
+    let l = parameter_values[0].unwrap_usize();
+    let k = parameter_values[1].unwrap_usize();
+    if l < min_l {
+        return Err(anyhow!(
+            r#"first parameter ("l") must be at least {}"#,
+            min_l
+        ));
+    }
+    if k < min_k {
+        return Err(anyhow!(
+            r#"second parameter ("k") must be at least {}"#,
+            min_k
+        ));
+    }
+    Ok((l, k))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+    use petgraph::{Directed, Undirected};
+    use rand::rngs::ThreadRng;
+
+    fn params(l: usize, k: usize) -> Vec<ParameterValue> {
+        vec![
+            ParameterValue::PositiveInteger(l),
+            ParameterValue::PositiveInteger(k),
+        ]
+    }
+
+    fn sorted_edges<Ty>(g: &Graph<Ty>) -> Vec<(NodeIndexType, NodeIndexType)>
+    where
+        Ty: EdgeType,
+    {
+        let mut edges = g
+            .iter_edges()
+            .map(|(a, b)| (usize::min(a, b), usize::max(a, b)))
+            .collect::<Vec<(NodeIndexType, NodeIndexType)>>();
+        edges.sort_unstable();
+        edges
+    }
+
+    #[test]
+    fn test_caveman_too_small() {
+        assert!(
+            (ConnectedCavemanGeneratorFactory.try_with_params(params(1, 3))
+                as Result<BoxedGenerator<Undirected, ThreadRng>>)
+                .is_err()
+        );
+        assert!(
+            (ConnectedCavemanGeneratorFactory.try_with_params(params(3, 2))
+                as Result<BoxedGenerator<Undirected, ThreadRng>>)
+                .is_err()
+        );
+        assert!((RingOfCliquesGeneratorFactory.try_with_params(params(3, 1))
+            as Result<BoxedGenerator<Undirected, ThreadRng>>)
+            .is_err());
+    }
+
+    #[test]
+    fn test_caveman() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = ConnectedCavemanGeneratorFactory
+            .try_with_params(params(2, 3))
+            .unwrap()(&mut rng);
+        assert_eq!(6, g.n_nodes());
+        assert_eq!(
+            vec![(0, 2), (0, 5), (1, 2), (2, 3), (3, 5), (4, 5)],
+            sorted_edges(&g)
+        );
+        assert_eq!(Some([0, 0, 0, 1, 1, 1].as_slice()), g.communities());
+    }
+
+    #[test]
+    fn test_ring_of_cliques() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = RingOfCliquesGeneratorFactory
+            .try_with_params(params(3, 2))
+            .unwrap()(&mut rng);
+        assert_eq!(6, g.n_nodes());
+        assert_eq!(
+            vec![(0, 1), (0, 5), (1, 2), (2, 3), (3, 4), (4, 5)],
+            sorted_edges(&g)
+        );
+    }
+
+    #[test]
+    fn test_relaxed_caveman_no_rewiring() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = RelaxedCavemanGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::PositiveInteger(4),
+                ParameterValue::Probability(0.),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(12, g.n_nodes());
+        assert_eq!(3 * 12, g.n_edges());
+        let communities = g.communities().unwrap();
+        assert!(g
+            .iter_edges()
+            .all(|(a, b)| communities[a] == communities[b]));
+    }
+
+    #[test]
+    fn test_relaxed_caveman() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = RelaxedCavemanGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(10),
+                ParameterValue::PositiveInteger(5),
+                ParameterValue::Probability(0.5),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(50, g.n_nodes());
+        assert_eq!(100, g.n_edges());
+        let mut edges = sorted_edges(&g);
+        assert!(edges.iter().all(|(a, b)| a != b));
+        edges.dedup();
+        assert_eq!(100, edges.len());
+    }
+}
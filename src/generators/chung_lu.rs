@@ -0,0 +1,398 @@
+use super::{
+    degree_sequences::{self, DegreeDistribution},
+    BoxedGenerator, GeneratorFactory,
+};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::EdgeType;
+use rand::{seq::SliceRandom, Rng};
+use rand_distr::{Distribution, Geometric};
+
+/// A factory used to build generators for graphs following the [Chung–Lu](https://doi.org/10.1073/pnas.252631999) expected degree model, given weights read from a file.
+///
+/// Two distinct nodes `i` and `j` are linked with probability `min(1, w_i*w_j/S)`, where `w_i` and `w_j` are the weights of the nodes and `S` is the sum of all the weights.
+/// The expected degree of each node is thus close to its weight.
+/// The edges are drawn using the algorithm of [Miller and Hagberg](https://doi.org/10.1007/978-3-642-21286-4_10), which runs in time linear in the size of the graph (after sorting the weights).
+///
+/// For undirected graphs, each non-empty line of the file must contain the weight of a node.
+/// For directed graphs, each non-empty line must contain the out-weight and the in-weight of a node, separated by a whitespace; the sums of both kinds of weights must be equal.
+/// In this case, an edge goes from `i` to `j` with probability `min(1, wout_i*win_j/S)`.
+/// Lines beginning with a `#` are ignored.
+///
+/// The weights are read once, when the factory builds the generator.
+///
+/// Such factories can be created by passing `chung_lu/f` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str),
+/// where `f` is the path to the file containing the weights.
+///
+/// Weights must be finite positive floating point numbers.
+#[derive(Default)]
+pub struct ChungLuGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for ChungLuGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "chung_lu"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the Chung-Lu expected degree model, using weights read from a file.",
+            "The first parameter is the path to the file; each line contains a weight (an out-weight and an in-weight for directed graphs).",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![ParameterType::String]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a Chung-Lu generator";
+        let path = parameter_values[0].unwrap_str();
+        let n_columns = if Ty::is_directed() { 2 } else { 1 };
+        let mut columns =
+            degree_sequences::read_columns::<f64>(path, n_columns).context(context)?;
+        if columns.iter().flatten().any(|w| !w.is_finite() || *w < 0.) {
+            return Err(anyhow!("weights must be finite positive numbers")).context(context);
+        }
+        let out_weights = columns.swap_remove(0);
+        let in_weights = columns.pop().unwrap_or_else(|| out_weights.clone());
+        let (out_sum, in_sum) = (
+            out_weights.iter().sum::<f64>(),
+            in_weights.iter().sum::<f64>(),
+        );
+        if (out_sum - in_sum).abs() > 1e-9 * f64::max(out_sum, in_sum) {
+            return Err(anyhow!(
+                "the sum of the out-weights must be equal to the sum of the in-weights"
+            ))
+            .context(context);
+        }
+        Ok(Box::new(move |r| build_graph(&out_weights, &in_weights, r)))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for ChungLuGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// A factory used to build generators for graphs following the [Chung–Lu](https://doi.org/10.1073/pnas.252631999) expected degree model, given a power law weight distribution.
+///
+/// A new weight sequence is drawn for each generated graph, following a power law which support is restricted to weights between 1 and `n-1`.
+/// For directed graphs, the drawn sequence gives the out-weights, and the in-weights are a random permutation of it.
+///
+/// See [`ChungLuGeneratorFactory`] for more information on the generation process.
+///
+/// Such factories can be created by passing `chung_lu_pl/n,g` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the size of graph to produce;
+///   - `g` is the exponent of the power law.
+#[derive(Default)]
+pub struct PowerLawChungLuGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for PowerLawChungLuGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "chung_lu_pl"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the Chung-Lu expected degree model, using weights drawn from a power law.",
+            "First parameter gives the number of nodes of the graph, while the second one gives the exponent of the power law.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![ParameterType::PositiveInteger, ParameterType::PositiveFloat]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let n = parameter_values[0].unwrap_usize();
+        let exponent = parameter_values[1].unwrap_f64();
+        with_distribution(n, DegreeDistribution::PowerLaw(exponent))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for PowerLawChungLuGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// A factory used to build generators for graphs following the [Chung–Lu](https://doi.org/10.1073/pnas.252631999) expected degree model, given a Poisson weight distribution.
+///
+/// A new weight sequence is drawn for each generated graph.
+/// For directed graphs, the drawn sequence gives the out-weights, and the in-weights are a random permutation of it.
+///
+/// See [`ChungLuGeneratorFactory`] for more information on the generation process.
+///
+/// Such factories can be created by passing `chung_lu_poisson/n,l` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the size of graph to produce;
+///   - `l` is the mean of the Poisson distribution.
+#[derive(Default)]
+pub struct PoissonChungLuGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for PoissonChungLuGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "chung_lu_poisson"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the Chung-Lu expected degree model, using weights drawn from a Poisson distribution.",
+            "First parameter gives the number of nodes of the graph, while the second one gives the mean weight.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![ParameterType::PositiveInteger, ParameterType::PositiveFloat]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let n = parameter_values[0].unwrap_usize();
+        let mean = parameter_values[1].unwrap_f64();
+        with_distribution(n, DegreeDistribution::Poisson(mean))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for PoissonChungLuGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// A factory used to build generators for graphs following the [Chung–Lu](https://doi.org/10.1073/pnas.252631999) expected degree model, given a uniform weight distribution.
+///
+/// A new weight sequence is drawn for each generated graph.
+/// For directed graphs, the drawn sequence gives the out-weights, and the in-weights are a random permutation of it.
+///
+/// See [`ChungLuGeneratorFactory`] for more information on the generation process.
+///
+/// Such factories can be created by passing `chung_lu_unif/n,a,b` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the size of graph to produce;
+///   - `a` is the minimal weight;
+///   - `b` is the maximal weight.
+///
+/// Parameter `a` must not be higher than `b`.
+#[derive(Default)]
+pub struct UniformChungLuGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for UniformChungLuGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "chung_lu_unif"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the Chung-Lu expected degree model, using weights drawn uniformly in a range.",
+            "First parameter gives the number of nodes of the graph, while the second and the third ones give the minimal and the maximal weights.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a Chung-Lu generator";
+        let n = parameter_values[0].unwrap_usize();
+        let min = parameter_values[1].unwrap_usize();
+        let max = parameter_values[2].unwrap_usize();
+        if min > max {
+            return Err(anyhow!(
+                r#"second parameter ("a") must not be higher than the third one ("b")"#
+            ))
+            .context(context);
+        }
+        with_distribution(n, DegreeDistribution::Uniform(min, max)).context(context)
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for UniformChungLuGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+fn with_distribution<Ty, R>(
+    n: usize,
+    distribution: DegreeDistribution,
+) -> Result<BoxedGenerator<Ty, R>>
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    let sampler = distribution.sampler(n)?;
+    Ok(Box::new(move |r| {
+        let out_weights = sampler
+            .sample_sequence(n, false, r)
+            .into_iter()
+            .map(|w| w as f64)
+            .collect::<Vec<f64>>();
+        let mut in_weights = out_weights.clone();
+        if Ty::is_directed() {
+            in_weights.shuffle(r);
+        }
+        build_graph(&out_weights, &in_weights, r)
+    }))
+}
+
+/// Builds a graph following the Chung–Lu model.
+///
+/// For undirected graphs, `in_weights` must be equal to `out_weights`.
+fn build_graph<Ty, R>(out_weights: &[f64], in_weights: &[f64], r: &mut R) -> Graph<Ty>
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    let n = out_weights.len();
+    let mut g = Graph::with_capacity(n, 0);
+    (0..n).for_each(|_| g.new_node());
+    let total = in_weights.iter().sum::<f64>();
+    if total == 0. {
+        return g;
+    }
+    let sorted_by_weight = |weights: &[f64]| {
+        let mut sorted = (0..n).collect::<Vec<usize>>();
+        sorted.sort_by(|i, j| weights[*j].total_cmp(&weights[*i]));
+        sorted
+    };
+    let sources = sorted_by_weight(out_weights);
+    let targets = sorted_by_weight(in_weights);
+    for (i, u) in sources.iter().enumerate() {
+        let first_target = if Ty::is_directed() { 0 } else { i + 1 };
+        let proba = |j: usize| f64::min(1., out_weights[*u] * in_weights[targets[j]] / total);
+        if first_target >= n {
+            continue;
+        }
+        let mut j = first_target;
+        let mut p = proba(j);
+        while j < n && p > 0. {
+            if p < 1. {
+                let skip = Geometric::new(p).unwrap().sample(r);
+                j = j.saturating_add(usize::try_from(skip).unwrap_or(usize::MAX));
+                if j >= n {
+                    break;
+                }
+            }
+            let q = proba(j);
+            if r.gen::<f64>() * p < q && targets[j] != *u {
+                g.new_edge(*u, targets[j]);
+            }
+            p = q;
+            j += 1;
+        }
+    }
+    g
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use petgraph::{Directed, Undirected};
+    use rand::rngs::ThreadRng;
+
+    #[test]
+    fn test_zero_weights() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = build_graph(&[0., 0., 0.], &[0., 0., 0.], &mut rng);
+        assert_eq!(3, g.n_nodes());
+        assert_eq!(0, g.n_edges());
+    }
+
+    #[test]
+    fn test_complete() {
+        let mut rng = rand::thread_rng();
+        let weights = [10.; 5];
+        let g: Graph<Undirected> = build_graph(&weights, &weights, &mut rng);
+        assert_eq!(10, g.n_edges());
+        let g: Graph<Directed> = build_graph(&weights, &weights, &mut rng);
+        assert_eq!(20, g.n_edges());
+        assert!(g.iter_edges().all(|(a, b)| a != b));
+    }
+
+    #[test]
+    fn test_expected_degrees() {
+        let mut rng = rand::thread_rng();
+        let n = 2000;
+        let weights = (0..n)
+            .map(|i| if i < 1000 { 2. } else { 20. })
+            .collect::<Vec<f64>>();
+        let g: Graph<Undirected> = build_graph(&weights, &weights, &mut rng);
+        let mut degrees = vec![0; n];
+        g.iter_edges().for_each(|(a, b)| {
+            degrees[a] += 1;
+            degrees[b] += 1;
+        });
+        let low_mean = degrees[..1000].iter().sum::<usize>() as f64 / 1000.;
+        let high_mean = degrees[1000..].iter().sum::<usize>() as f64 / 1000.;
+        assert!((1.5..2.5).contains(&low_mean));
+        assert!((18. ..22.).contains(&high_mean));
+    }
+
+    #[test]
+    fn test_file() {
+        let path = std::env::temp_dir().join(format!(
+            "crusti_g2io_test_chung_lu_file_{}.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, "# weights\n1 0\n0.5 1\n\n0.5 1\n").unwrap();
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = ChungLuGeneratorFactory
+            .try_with_params(vec![ParameterValue::String(
+                path.to_str().unwrap().to_string(),
+            )])
+            .unwrap()(&mut rng);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(3, g.n_nodes());
+        assert!(g.iter_edges().all(|(a, b)| a != b && b != 0));
+    }
+
+    #[test]
+    fn test_file_wrong_sums() {
+        let path = std::env::temp_dir().join(format!(
+            "crusti_g2io_test_chung_lu_file_sums_{}.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, "1 2\n2 2\n").unwrap();
+        let result = ChungLuGeneratorFactory.try_with_params(vec![ParameterValue::String(
+            path.to_str().unwrap().to_string(),
+        )]) as Result<BoxedGenerator<Directed, ThreadRng>>;
+        std::fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+}
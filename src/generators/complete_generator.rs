@@ -0,0 +1,116 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::Result;
+use petgraph::EdgeType;
+use rand::Rng;
+use std::ops::Range;
+
+/// A factory used to build generators for complete graphs.
+///
+/// In directed graphs generated by this objects, each pair of nodes is linked by two edges, one in each direction.
+///
+/// Such factories can be created by passing `complete/n` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str),
+/// where `n` is the number of nodes and must be at least 0.
+#[derive(Default)]
+pub struct CompleteGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for CompleteGeneratorFactory
+where
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "complete"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a complete graph.",
+            "The first parameter gives the number of nodes.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![ParameterType::PositiveInteger]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let n = parameter_values[0].unwrap_usize();
+        Ok(Box::new(move |_| {
+            let mut g = Graph::with_capacity(n, n * n.saturating_sub(1));
+            (0..n).for_each(|_| g.new_node());
+            add_clique(&mut g, 0..n);
+            g
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for CompleteGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// Links all the pairs of nodes which labels are in the given range.
+///
+/// In directed graphs, two edges are added for each pair of nodes, one in each direction.
+pub(crate) fn add_clique<Ty>(g: &mut Graph<Ty>, nodes: Range<usize>)
+where
+    Ty: EdgeType,
+{
+    for i in nodes.clone() {
+        for j in i + 1..nodes.end {
+            g.new_edge(i, j);
+            if Ty::is_directed() {
+                g.new_edge(j, i);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+    use petgraph::{Directed, Undirected};
+
+    #[test]
+    fn test_complete_of_one() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = CompleteGeneratorFactory
+            .try_with_params(vec![ParameterValue::PositiveInteger(1)])
+            .unwrap()(&mut rng);
+        assert_eq!(1, g.n_nodes());
+        assert_eq!(0, g.n_edges());
+    }
+
+    #[test]
+    fn test_complete_undirected() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = CompleteGeneratorFactory
+            .try_with_params(vec![ParameterValue::PositiveInteger(3)])
+            .unwrap()(&mut rng);
+        assert_eq!(3, g.n_nodes());
+        assert_eq!(
+            vec![(0, 1), (0, 2), (1, 2)],
+            g.iter_edges()
+                .collect::<Vec<(NodeIndexType, NodeIndexType)>>()
+        );
+    }
+
+    #[test]
+    fn test_complete_directed() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = CompleteGeneratorFactory
+            .try_with_params(vec![ParameterValue::PositiveInteger(3)])
+            .unwrap()(&mut rng);
+        let mut edges = g
+            .iter_edges()
+            .collect::<Vec<(NodeIndexType, NodeIndexType)>>();
+        edges.sort_unstable();
+        assert_eq!(vec![(0, 1), (0, 2), (1, 0), (1, 2), (2, 0), (2, 1)], edges);
+    }
+}
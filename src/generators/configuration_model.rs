@@ -0,0 +1,492 @@
+use super::{
+    degree_sequences::{self, DegreeDistribution},
+    BoxedGenerator, GeneratorFactory,
+};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::EdgeType;
+use rand::{seq::SliceRandom, Rng};
+use std::collections::HashSet;
+
+/// A factory used to build generators for graphs following the [configuration model](https://en.wikipedia.org/wiki/Configuration_model), given a degree sequence read from a file.
+///
+/// For undirected graphs, each non-empty line of the file must contain the degree of a node; the sum of the degrees must be even.
+/// For directed graphs, each non-empty line must contain the out-degree and the in-degree of a node, separated by a whitespace; the sums of both kinds of degrees must be equal.
+/// Lines beginning with a `#` are ignored.
+///
+/// The degree sequence is read once, when the factory builds the generator.
+/// The graph is then built by randomly matching the edge endpoints (the "stubs") of the nodes.
+/// The resulting graph may contain self-loops and multiple edges, unless the optional parameter `erase` is set to `true`;
+/// in this case, the self-loops and the multiple edges are removed (and the degree sequence may not be exactly matched).
+///
+/// Such factories can be created by passing `config/f` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str),
+/// where `f` is the path to the file containing the degree sequence.
+#[derive(Default)]
+pub struct ConfigurationModelGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for ConfigurationModelGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "config"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the configuration model, using a degree sequence read from a file.",
+            "The first parameter is the path to the file; each line contains a degree (an out-degree and an in-degree for directed graphs).",
+            "Self-loops and multiple edges are removed if the optional parameter \"erase\" is set to true.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![ParameterType::String]
+    }
+
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        erase_optional_parameter()
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a configuration model generator";
+        let path = parameter_values[0].unwrap_str();
+        let erase = parameter_values[1].unwrap_bool();
+        let (out_degrees, in_degrees) = if Ty::is_directed() {
+            let mut columns = degree_sequences::read_columns::<usize>(path, 2).context(context)?;
+            let in_degrees = columns.pop().unwrap();
+            let out_degrees = columns.pop().unwrap();
+            if out_degrees.iter().sum::<usize>() != in_degrees.iter().sum::<usize>() {
+                return Err(anyhow!(
+                    "the sum of the out-degrees must be equal to the sum of the in-degrees"
+                ))
+                .context(context);
+            }
+            (out_degrees, in_degrees)
+        } else {
+            let degrees = degree_sequences::read_columns::<usize>(path, 1)
+                .context(context)?
+                .pop()
+                .unwrap();
+            if degrees.iter().sum::<usize>() & 1 == 1 {
+                return Err(anyhow!("the sum of the degrees must be even")).context(context);
+            }
+            (degrees, vec![])
+        };
+        Ok(Box::new(move |r| {
+            build_graph(&out_degrees, &in_degrees, erase, r)
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for ConfigurationModelGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// A factory used to build generators for graphs following the [configuration model](https://en.wikipedia.org/wiki/Configuration_model), given a power law degree distribution.
+///
+/// A new degree sequence is drawn for each generated graph, following a power law which support is restricted to degrees between 1 and `n-1`.
+/// For undirected graphs, degrees are drawn again until their sum is even.
+/// For directed graphs, the drawn sequence gives the out-degrees, and the in-degrees are a random permutation of it.
+///
+/// See [`ConfigurationModelGeneratorFactory`] for more information on the generation process and the `erase` optional parameter.
+///
+/// Such factories can be created by passing `config_pl/n,g` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the size of graph to produce;
+///   - `g` is the exponent of the power law.
+#[derive(Default)]
+pub struct PowerLawConfigurationModelGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for PowerLawConfigurationModelGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "config_pl"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the configuration model, using degrees drawn from a power law.",
+            "First parameter gives the number of nodes of the graph, while the second one gives the exponent of the power law.",
+            "Self-loops and multiple edges are removed if the optional parameter \"erase\" is set to true.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![ParameterType::PositiveInteger, ParameterType::PositiveFloat]
+    }
+
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        erase_optional_parameter()
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let n = parameter_values[0].unwrap_usize();
+        let exponent = parameter_values[1].unwrap_f64();
+        let erase = parameter_values[2].unwrap_bool();
+        try_with_distribution(n, DegreeDistribution::PowerLaw(exponent), erase)
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for PowerLawConfigurationModelGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// A factory used to build generators for graphs following the [configuration model](https://en.wikipedia.org/wiki/Configuration_model), given a Poisson degree distribution.
+///
+/// A new degree sequence is drawn for each generated graph.
+/// For undirected graphs, degrees are drawn again until their sum is even.
+/// For directed graphs, the drawn sequence gives the out-degrees, and the in-degrees are a random permutation of it.
+///
+/// See [`ConfigurationModelGeneratorFactory`] for more information on the generation process and the `erase` optional parameter.
+///
+/// Such factories can be created by passing `config_poisson/n,l` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the size of graph to produce;
+///   - `l` is the mean of the Poisson distribution.
+#[derive(Default)]
+pub struct PoissonConfigurationModelGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for PoissonConfigurationModelGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "config_poisson"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the configuration model, using degrees drawn from a Poisson distribution.",
+            "First parameter gives the number of nodes of the graph, while the second one gives the mean degree.",
+            "Self-loops and multiple edges are removed if the optional parameter \"erase\" is set to true.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![ParameterType::PositiveInteger, ParameterType::PositiveFloat]
+    }
+
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        erase_optional_parameter()
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let n = parameter_values[0].unwrap_usize();
+        let mean = parameter_values[1].unwrap_f64();
+        let erase = parameter_values[2].unwrap_bool();
+        try_with_distribution(n, DegreeDistribution::Poisson(mean), erase)
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for PoissonConfigurationModelGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// A factory used to build generators for graphs following the [configuration model](https://en.wikipedia.org/wiki/Configuration_model), given a uniform degree distribution.
+///
+/// A new degree sequence is drawn for each generated graph.
+/// For undirected graphs, degrees are drawn again until their sum is even.
+/// For directed graphs, the drawn sequence gives the out-degrees, and the in-degrees are a random permutation of it.
+///
+/// See [`ConfigurationModelGeneratorFactory`] for more information on the generation process and the `erase` optional parameter.
+///
+/// Such factories can be created by passing `config_unif/n,a,b` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the size of graph to produce;
+///   - `a` is the minimal degree;
+///   - `b` is the maximal degree.
+///
+/// Parameter `a` must not be higher than `b`.
+#[derive(Default)]
+pub struct UniformConfigurationModelGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for UniformConfigurationModelGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "config_unif"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the configuration model, using degrees drawn uniformly in a range.",
+            "First parameter gives the number of nodes of the graph, while the second and the third ones give the minimal and the maximal degrees.",
+            "Self-loops and multiple edges are removed if the optional parameter \"erase\" is set to true.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+        ]
+    }
+
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        erase_optional_parameter()
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a configuration model generator";
+        let n = parameter_values[0].unwrap_usize();
+        let min = parameter_values[1].unwrap_usize();
+        let max = parameter_values[2].unwrap_usize();
+        let erase = parameter_values[3].unwrap_bool();
+        if min > max {
+            return Err(anyhow!(
+                r#"second parameter ("a") must not be higher than the third one ("b")"#
+            ))
+            .context(context);
+        }
+        try_with_distribution(n, DegreeDistribution::Uniform(min, max), erase).context(context)
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for UniformConfigurationModelGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+fn erase_optional_parameter() -> Vec<(&'static str, ParameterType, ParameterValue)> {
+    vec![(
+        "erase",
+        ParameterType::Boolean,
+        ParameterValue::Boolean(false),
+    )]
+}
+
+fn try_with_distribution<Ty, R>(
+    n: usize,
+    distribution: DegreeDistribution,
+    erase: bool,
+) -> Result<BoxedGenerator<Ty, R>>
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    if !Ty::is_directed() {
+        distribution.check_even_sum(n)?;
+    }
+    let sampler = distribution.sampler(n)?;
+    Ok(Box::new(move |r| {
+        let out_degrees = sampler.sample_sequence(n, !Ty::is_directed(), r);
+        let in_degrees = if Ty::is_directed() {
+            let mut in_degrees = out_degrees.clone();
+            in_degrees.shuffle(r);
+            in_degrees
+        } else {
+            vec![]
+        };
+        build_graph(&out_degrees, &in_degrees, erase, r)
+    }))
+}
+
+/// Builds a graph following the configuration model.
+///
+/// For undirected graphs, `in_degrees` is ignored and `out_degrees` gives the degrees of the nodes.
+pub(crate) fn build_graph<Ty, R>(
+    out_degrees: &[usize],
+    in_degrees: &[usize],
+    erase: bool,
+    r: &mut R,
+) -> Graph<Ty>
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    let n = usize::max(out_degrees.len(), in_degrees.len());
+    let stubs = |degrees: &[usize]| {
+        degrees
+            .iter()
+            .enumerate()
+            .flat_map(|(i, d)| std::iter::repeat_n(i, *d))
+            .collect::<Vec<usize>>()
+    };
+    let out_stubs = stubs(out_degrees);
+    let edges: Vec<(usize, usize)> = if Ty::is_directed() {
+        let mut in_stubs = stubs(in_degrees);
+        in_stubs.shuffle(r);
+        out_stubs.into_iter().zip(in_stubs).collect()
+    } else {
+        let mut stubs = out_stubs;
+        stubs.shuffle(r);
+        stubs.chunks_exact(2).map(|c| (c[0], c[1])).collect()
+    };
+    let mut g = Graph::with_capacity(n, edges.len());
+    (0..n).for_each(|_| g.new_node());
+    let mut added = HashSet::with_capacity(if erase { edges.len() } else { 0 });
+    for (from, to) in edges {
+        if erase {
+            let key = if Ty::is_directed() || from < to {
+                (from, to)
+            } else {
+                (to, from)
+            };
+            if from == to || !added.insert(key) {
+                continue;
+            }
+        }
+        g.new_edge(from, to);
+    }
+    g
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+    use petgraph::{Directed, Undirected};
+    use rand::rngs::ThreadRng;
+
+    fn degrees<Ty>(g: &Graph<Ty>) -> (Vec<usize>, Vec<usize>)
+    where
+        Ty: EdgeType,
+    {
+        let mut out_degrees = vec![0; g.n_nodes()];
+        let mut in_degrees = vec![0; g.n_nodes()];
+        g.iter_edges().for_each(|(from, to)| {
+            out_degrees[from] += 1;
+            in_degrees[to] += 1;
+        });
+        (out_degrees, in_degrees)
+    }
+
+    #[test]
+    fn test_undirected_degrees() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = build_graph(&[3, 2, 2, 1], &[], false, &mut rng);
+        assert_eq!(4, g.n_nodes());
+        assert_eq!(4, g.n_edges());
+        let (out_degrees, in_degrees) = degrees(&g);
+        let total = out_degrees
+            .iter()
+            .zip(in_degrees.iter())
+            .map(|(o, i)| o + i)
+            .collect::<Vec<usize>>();
+        assert_eq!(vec![3, 2, 2, 1], total);
+    }
+
+    #[test]
+    fn test_directed_degrees() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = build_graph(&[2, 0, 1], &[1, 1, 1], false, &mut rng);
+        assert_eq!((vec![2, 0, 1], vec![1, 1, 1]), degrees(&g));
+    }
+
+    #[test]
+    fn test_erase() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = build_graph(&[2], &[], true, &mut rng);
+        assert_eq!(1, g.n_nodes());
+        assert_eq!(0, g.n_edges());
+        let g: Graph<Directed> = build_graph(&[2, 0], &[0, 2], true, &mut rng);
+        assert_eq!(
+            vec![(0, 1)],
+            g.iter_edges()
+                .collect::<Vec<(NodeIndexType, NodeIndexType)>>()
+        );
+    }
+
+    #[test]
+    fn test_file() {
+        let path = std::env::temp_dir().join(format!(
+            "crusti_g2io_test_config_file_{}.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, "# degrees\n1\n2\n\n1\n").unwrap();
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = ConfigurationModelGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::String(path.to_str().unwrap().to_string()),
+                ParameterValue::Boolean(false),
+            ])
+            .unwrap()(&mut rng);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(3, g.n_nodes());
+        assert_eq!(2, g.n_edges());
+    }
+
+    #[test]
+    fn test_file_odd_sum() {
+        let path = std::env::temp_dir().join(format!(
+            "crusti_g2io_test_config_file_odd_{}.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, "1\n2\n").unwrap();
+        let result = ConfigurationModelGeneratorFactory.try_with_params(vec![
+            ParameterValue::String(path.to_str().unwrap().to_string()),
+            ParameterValue::Boolean(false),
+        ]) as Result<BoxedGenerator<Undirected, ThreadRng>>;
+        std::fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_uniform_min_higher_than_max() {
+        assert!(
+            (UniformConfigurationModelGeneratorFactory.try_with_params(vec![
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::PositiveInteger(1),
+                ParameterValue::Boolean(false),
+            ]) as Result<BoxedGenerator<Undirected, ThreadRng>>)
+                .is_err()
+        )
+    }
+
+    #[test]
+    fn test_uniform_regular() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = UniformConfigurationModelGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(5),
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::Boolean(false),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!((vec![2; 5], vec![2; 5]), degrees(&g));
+    }
+
+    #[test]
+    fn test_power_law_node_count() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = PowerLawConfigurationModelGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(50),
+                ParameterValue::PositiveFloat(2.5),
+                ParameterValue::Boolean(true),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(50, g.n_nodes());
+    }
+}
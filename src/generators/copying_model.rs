@@ -0,0 +1,158 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::Directed;
+use rand::{
+    distributions::{Distribution, Uniform},
+    Rng,
+};
+
+/// A factory used to build generators for graphs following the [copying model](https://doi.org/10.1109/SFCS.2000.892065) of Kumar et al.
+///
+/// The graph is initialized with `d+1` nodes, each of them linked to all the others.
+/// Then, nodes are added one at a time, each of them with `d` outgoing edges.
+/// Each new node chooses a prototype node uniformly at random;
+/// then, for each `i` in `1..=d`, the `i`-th edge goes to a node chosen uniformly at random with probability `a`,
+/// and to the target of the `i`-th edge of the prototype otherwise.
+/// Multiple edges are discarded, so some nodes may have less than `d` outgoing edges.
+///
+/// This generator is only available for directed graphs.
+///
+/// Such factories can be created by passing `copy/n,d,a` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the number of nodes;
+///   - `d` is the number of outgoing edges of each node;
+///   - `a` is the probability to choose a random target instead of copying the one of the prototype.
+///
+/// Parameter `d` must be higher than 0, and `n` must be higher than `d`.
+#[derive(Default)]
+pub struct CopyingModelGeneratorFactory;
+
+impl<R> NamedParam<BoxedGenerator<Directed, R>> for CopyingModelGeneratorFactory
+where
+    R: Rng,
+{
+    fn name(&self) -> &'static str {
+        "copy"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the copying model.",
+            "First parameter gives the number of nodes, the second one gives the number of outgoing edges per node, and the third one gives the probability to choose a random target instead of copying one.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+            ParameterType::Probability,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Directed, R>> {
+        let context = "while building a copying model generator";
+        let n = parameter_values[0].unwrap_usize();
+        let d = parameter_values[1].unwrap_usize();
+        let a = parameter_values[2].unwrap_f64();
+        if d == 0 || d >= n {
+            return Err(anyhow!(
+                r#"second parameter ("d") must be higher than 0 and lower than the first one ("n")"#
+            ))
+            .context(context);
+        }
+        Ok(Box::new(move |r| build_graph(n, d, a, r)))
+    }
+}
+
+impl<R> GeneratorFactory<Directed, R> for CopyingModelGeneratorFactory where R: Rng {}
+
+fn build_graph<R>(n: usize, d: usize, a: f64, r: &mut R) -> Graph<Directed>
+where
+    R: Rng,
+{
+    let mut g = Graph::with_capacity(n, n * d);
+    (0..n).for_each(|_| g.new_node());
+    let mut out_neighbors: Vec<Vec<usize>> = Vec::with_capacity(n);
+    for i in 0..=d {
+        let targets = (0..=d).filter(|j| *j != i).collect::<Vec<usize>>();
+        targets.iter().for_each(|j| g.new_edge(i, *j));
+        out_neighbors.push(targets);
+    }
+    let proba_uniform = Uniform::new_inclusive(0., 1.);
+    for new_node in d + 1..n {
+        let prototype = r.gen_range(0..new_node);
+        let mut targets = Vec::with_capacity(d);
+        for i in 0..d {
+            let target = if proba_uniform.sample(r) < a {
+                r.gen_range(0..new_node)
+            } else {
+                out_neighbors[prototype][i % out_neighbors[prototype].len()]
+            };
+            if !targets.contains(&target) {
+                g.new_edge(new_node, target);
+                targets.push(target);
+            }
+        }
+        out_neighbors.push(targets);
+    }
+    g
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+    use rand::rngs::ThreadRng;
+
+    #[test]
+    fn test_d_is_not_lower_than_n() {
+        assert!((CopyingModelGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(3),
+            ParameterValue::PositiveInteger(3),
+            ParameterValue::Probability(0.5),
+        ]) as Result<BoxedGenerator<Directed, ThreadRng>>)
+            .is_err())
+    }
+
+    #[test]
+    fn test_pure_copy() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = CopyingModelGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(10),
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::Probability(0.),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(10, g.n_nodes());
+        assert_eq!(20, g.n_edges());
+        assert!(g.iter_edges().all(|(_, b)| b < 3));
+    }
+
+    #[test]
+    fn test_simple_graph() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = CopyingModelGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(100),
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::Probability(0.5),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(100, g.n_nodes());
+        let mut edges = g
+            .iter_edges()
+            .collect::<Vec<(NodeIndexType, NodeIndexType)>>();
+        edges.sort_unstable();
+        edges.dedup();
+        assert_eq!(g.n_edges(), edges.len());
+        assert!(edges.iter().all(|(a, b)| a != b));
+        let mut out_degrees = vec![0; 100];
+        edges.iter().for_each(|(a, _)| out_degrees[*a] += 1);
+        assert!(out_degrees.into_iter().all(|d| (1..=3).contains(&d)));
+    }
+}
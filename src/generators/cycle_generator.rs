@@ -0,0 +1,127 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::Result;
+use petgraph::EdgeType;
+use rand::Rng;
+use std::ops::Range;
+
+/// A factory used to build generators for cycle graphs.
+///
+/// In directed graphs generated by this objects, edges go from nodes to the ones with the same index plus one, and from the last node to the first one.
+///
+/// Cycles of one node have no edge; undirected cycles of two nodes have a single edge.
+///
+/// Such factories can be created by passing `cycle/n` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str),
+/// where `n` is the number of nodes and must be at least 0.
+#[derive(Default)]
+pub struct CycleGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for CycleGeneratorFactory
+where
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "cycle"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a cycle graph.",
+            "The first parameter gives the number of nodes.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![ParameterType::PositiveInteger]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let n = parameter_values[0].unwrap_usize();
+        Ok(Box::new(move |_| {
+            let mut g = Graph::with_capacity(n, n);
+            (0..n).for_each(|_| g.new_node());
+            add_cycle(&mut g, 0..n);
+            g
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for CycleGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// Adds a cycle going through the nodes which labels are in the given range, in increasing order.
+///
+/// No edge is added for a single node, and a single edge is added for two nodes in undirected graphs.
+pub(crate) fn add_cycle<Ty>(g: &mut Graph<Ty>, nodes: Range<usize>)
+where
+    Ty: EdgeType,
+{
+    if nodes.len() < 2 {
+        return;
+    }
+    for i in nodes.start..nodes.end - 1 {
+        g.new_edge(i, i + 1);
+    }
+    if nodes.len() > 2 || Ty::is_directed() {
+        g.new_edge(nodes.end - 1, nodes.start);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+    use petgraph::{Directed, Undirected};
+
+    #[test]
+    fn test_cycle_of_one() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = CycleGeneratorFactory
+            .try_with_params(vec![ParameterValue::PositiveInteger(1)])
+            .unwrap()(&mut rng);
+        assert_eq!(1, g.n_nodes());
+        assert_eq!(0, g.n_edges());
+    }
+
+    #[test]
+    fn test_cycle_of_two() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = CycleGeneratorFactory
+            .try_with_params(vec![ParameterValue::PositiveInteger(2)])
+            .unwrap()(&mut rng);
+        assert_eq!(
+            vec![(0, 1)],
+            g.iter_edges()
+                .collect::<Vec<(NodeIndexType, NodeIndexType)>>()
+        );
+        let g: Graph<Directed> = CycleGeneratorFactory
+            .try_with_params(vec![ParameterValue::PositiveInteger(2)])
+            .unwrap()(&mut rng);
+        assert_eq!(
+            vec![(0, 1), (1, 0)],
+            g.iter_edges()
+                .collect::<Vec<(NodeIndexType, NodeIndexType)>>()
+        );
+    }
+
+    #[test]
+    fn test_cycle() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = CycleGeneratorFactory
+            .try_with_params(vec![ParameterValue::PositiveInteger(4)])
+            .unwrap()(&mut rng);
+        assert_eq!(4, g.n_nodes());
+        assert_eq!(
+            vec![(0, 1), (1, 2), (2, 3), (3, 0)],
+            g.iter_edges()
+                .collect::<Vec<(NodeIndexType, NodeIndexType)>>()
+        );
+    }
+}
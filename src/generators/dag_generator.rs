@@ -0,0 +1,254 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::Result;
+use petgraph::Directed;
+use rand::{
+    distributions::{Distribution, Uniform},
+    seq::{index, SliceRandom},
+    Rng,
+};
+
+/// A factory used to build generators for random directed acyclic graphs.
+///
+/// A random topological order is first drawn among the nodes.
+/// Then, each edge going from a node to one of its successors in this order is added with a given probability.
+///
+/// The number of incoming edges of each node can be bounded using the `max_in` optional parameter (e.g. `dag/n,p,max_in=3`).
+/// When the bound is exceeded, the incoming edges to keep are drawn uniformly among the selected ones.
+/// Setting it to 0, which is its default value, means no bound is applied.
+///
+/// This generator is only available for directed graphs.
+///
+/// Such factories can be created by passing `dag/n,p` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the number of nodes;
+///   - `p` is the probability each edge compatible with the topological order appears in the graph.
+#[derive(Default)]
+pub struct RandomDagGeneratorFactory;
+
+impl<R> NamedParam<BoxedGenerator<Directed, R>> for RandomDagGeneratorFactory
+where
+    R: Rng,
+{
+    fn name(&self) -> &'static str {
+        "dag"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a random directed acyclic graph.",
+            "First parameter gives the number of nodes, while the second one gives the probability each edge compatible with a random topological order appears in the graph.",
+            "The in-degree of the nodes is bounded by the optional parameter \"max_in\" (0 means no bound).",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![ParameterType::PositiveInteger, ParameterType::Probability]
+    }
+
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        max_in_optional_parameter()
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Directed, R>> {
+        let n = parameter_values[0].unwrap_usize();
+        let p = parameter_values[1].unwrap_f64();
+        let max_in = parameter_values[2].unwrap_usize();
+        Ok(Box::new(move |r| {
+            let mut g = Graph::with_capacity(n, 0);
+            (0..n).for_each(|_| g.new_node());
+            let mut order = (0..n).collect::<Vec<usize>>();
+            order.shuffle(r);
+            for (i, target) in order.iter().enumerate() {
+                add_incoming_edges(&mut g, &order[..i], *target, p, max_in, r);
+            }
+            g
+        }))
+    }
+}
+
+impl<R> GeneratorFactory<Directed, R> for RandomDagGeneratorFactory where R: Rng {}
+
+/// A factory used to build generators for layered directed acyclic graphs.
+///
+/// Nodes are split into layers of the same width; the node at position `i` of layer `k` has label `k*w+i`.
+/// Each edge going from a node of a layer to a node of the next one is added with a given probability.
+/// The longest paths of the graphs thus contain at most as many nodes as there are layers.
+///
+/// The number of incoming edges of each node can be bounded using the `max_in` optional parameter (e.g. `dag_layered/l,w,p,max_in=3`).
+/// When the bound is exceeded, the incoming edges to keep are drawn uniformly among the selected ones.
+/// Setting it to 0, which is its default value, means no bound is applied.
+///
+/// This generator is only available for directed graphs.
+///
+/// Such factories can be created by passing `dag_layered/l,w,p` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `l` is the number of layers;
+///   - `w` is the number of nodes in each layer;
+///   - `p` is the probability each edge between consecutive layers appears in the graph.
+#[derive(Default)]
+pub struct LayeredDagGeneratorFactory;
+
+impl<R> NamedParam<BoxedGenerator<Directed, R>> for LayeredDagGeneratorFactory
+where
+    R: Rng,
+{
+    fn name(&self) -> &'static str {
+        "dag_layered"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a layered directed acyclic graph.",
+            "First parameter gives the number of layers, the second one gives their width, and the third one gives the probability each edge between consecutive layers appears in the graph.",
+            "The in-degree of the nodes is bounded by the optional parameter \"max_in\" (0 means no bound).",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+            ParameterType::Probability,
+        ]
+    }
+
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        max_in_optional_parameter()
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Directed, R>> {
+        let n_layers = parameter_values[0].unwrap_usize();
+        let width = parameter_values[1].unwrap_usize();
+        let p = parameter_values[2].unwrap_f64();
+        let max_in = parameter_values[3].unwrap_usize();
+        Ok(Box::new(move |r| {
+            let n = n_layers * width;
+            let mut g = Graph::with_capacity(n, 0);
+            (0..n).for_each(|_| g.new_node());
+            for layer in 1..n_layers {
+                let sources = ((layer - 1) * width..layer * width).collect::<Vec<usize>>();
+                for target in layer * width..(layer + 1) * width {
+                    add_incoming_edges(&mut g, &sources, target, p, max_in, r);
+                }
+            }
+            g
+        }))
+    }
+}
+
+impl<R> GeneratorFactory<Directed, R> for LayeredDagGeneratorFactory where R: Rng {}
+
+fn max_in_optional_parameter() -> Vec<(&'static str, ParameterType, ParameterValue)> {
+    vec![(
+        "max_in",
+        ParameterType::PositiveInteger,
+        ParameterValue::PositiveInteger(0),
+    )]
+}
+
+/// Adds edges from the candidate sources to the target, each with probability `p`.
+///
+/// If more than `max_in` edges are selected (and `max_in` is not 0), only `max_in` of them are kept at random.
+fn add_incoming_edges<R>(
+    g: &mut Graph<Directed>,
+    sources: &[usize],
+    target: usize,
+    p: f64,
+    max_in: usize,
+    r: &mut R,
+) where
+    R: Rng,
+{
+    let proba_uniform = Uniform::new_inclusive(0., 1.);
+    let selected = sources
+        .iter()
+        .filter(|_| proba_uniform.sample(r) < p)
+        .copied()
+        .collect::<Vec<usize>>();
+    if max_in == 0 || selected.len() <= max_in {
+        selected.into_iter().for_each(|s| g.new_edge(s, target));
+    } else {
+        let mut kept = index::sample(r, selected.len(), max_in).into_vec();
+        kept.sort_unstable();
+        kept.into_iter()
+            .for_each(|i| g.new_edge(selected[i], target));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+
+    fn assert_is_acyclic(g: &Graph<Directed>) {
+        let petgraph_g =
+            petgraph::Graph::<(), (), Directed, NodeIndexType>::from_edges(g.iter_edges());
+        assert!(!petgraph::algo::is_cyclic_directed(&petgraph_g));
+    }
+
+    #[test]
+    fn test_dag_probability_1() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = RandomDagGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(10),
+                ParameterValue::Probability(1.0),
+                ParameterValue::PositiveInteger(0),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(10, g.n_nodes());
+        assert_eq!(45, g.n_edges());
+        assert_is_acyclic(&g);
+    }
+
+    #[test]
+    fn test_dag_max_in() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = RandomDagGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(10),
+                ParameterValue::Probability(1.0),
+                ParameterValue::PositiveInteger(2),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(10, g.n_nodes());
+        assert_eq!(17, g.n_edges());
+        let mut in_degrees = vec![0; 10];
+        g.iter_edges().for_each(|(_, b)| in_degrees[b] += 1);
+        assert!(in_degrees.into_iter().all(|d| d <= 2));
+        assert_is_acyclic(&g);
+    }
+
+    #[test]
+    fn test_layered_dag() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = LayeredDagGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::Probability(1.0),
+                ParameterValue::PositiveInteger(0),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(6, g.n_nodes());
+        assert_eq!(
+            vec![
+                (0, 2),
+                (1, 2),
+                (0, 3),
+                (1, 3),
+                (2, 4),
+                (3, 4),
+                (2, 5),
+                (3, 5)
+            ],
+            g.iter_edges()
+                .collect::<Vec<(NodeIndexType, NodeIndexType)>>()
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::Directed;
+use rand::Rng;
+
+/// A factory used to build generators for [de Bruijn graphs](https://en.wikipedia.org/wiki/De_Bruijn_graph).
+///
+/// The nodes of the de Bruijn graph `B(d,n)` are the words of length `n` over an alphabet of `d` symbols,
+/// labeled by their value when read as numbers in base `d`.
+/// There is an edge from each word `s_1...s_n` to each word `s_2...s_n a`, for every symbol `a`.
+/// Each node has thus `d` outgoing edges; the `d` words made of a single repeated symbol have a self-loop.
+///
+/// This generator is only available for directed graphs.
+///
+/// Such factories can be created by passing `debruijn/d,n` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `d` is the number of symbols;
+///   - `n` is the length of the words.
+///
+/// Both parameters must be at least 1.
+#[derive(Default)]
+pub struct DeBruijnGeneratorFactory;
+
+impl<R> NamedParam<BoxedGenerator<Directed, R>> for DeBruijnGeneratorFactory {
+    fn name(&self) -> &'static str {
+        "debruijn"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a de Bruijn graph.",
+            "First parameter gives the number of symbols, while the second one gives the length of the words.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Directed, R>> {
+        let context = "while building a de Bruijn generator";
+        let d = parameter_values[0].unwrap_usize();
+        let n = parameter_values[1].unwrap_usize();
+        if d == 0 || n == 0 {
+            return Err(anyhow!(r#"parameters ("d" and "n") must be at least 1"#)).context(context);
+        }
+        let n_nodes = checked_pow(d, n).context(context)?;
+        let n_edges = checked_n_edges(n_nodes, d).context(context)?;
+        Ok(Box::new(move |_| {
+            let mut g = Graph::with_capacity(n_nodes, n_edges);
+            (0..n_nodes).for_each(|_| g.new_node());
+            for word in 0..n_nodes {
+                let shifted = (word % (n_nodes / d)) * d;
+                (0..d).for_each(|a| g.new_edge(word, shifted + a));
+            }
+            g
+        }))
+    }
+}
+
+impl<R> GeneratorFactory<Directed, R> for DeBruijnGeneratorFactory where R: Rng {}
+
+/// A factory used to build generators for [Kautz graphs](https://en.wikipedia.org/wiki/Kautz_graph).
+///
+/// The nodes of the Kautz graph `K(d,n)` are the words of length `n+1` over an alphabet of `d+1` symbols in which two consecutive symbols are always different.
+/// There are `(d+1)*d^n` such words, labeled in lexicographic order.
+/// There is an edge from each word `s_0...s_n` to each word `s_1...s_n a`, for every symbol `a` different from `s_n`.
+/// Each node has thus `d` outgoing edges, and the graph has no self-loop.
+///
+/// This generator is only available for directed graphs.
+///
+/// Such factories can be created by passing `kautz/d,n` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `d` is the out-degree of the nodes (the alphabet has `d+1` symbols);
+///   - `n` is the length of the words minus one.
+///
+/// Parameter `d` must be at least 1.
+#[derive(Default)]
+pub struct KautzGeneratorFactory;
+
+impl<R> NamedParam<BoxedGenerator<Directed, R>> for KautzGeneratorFactory {
+    fn name(&self) -> &'static str {
+        "kautz"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a Kautz graph.",
+            "First parameter gives the out-degree of the nodes, while the second one gives the length of the words minus one.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Directed, R>> {
+        let context = "while building a Kautz generator";
+        let d = parameter_values[0].unwrap_usize();
+        let n = parameter_values[1].unwrap_usize();
+        if d == 0 {
+            return Err(anyhow!(r#"first parameter ("d") must be at least 1"#)).context(context);
+        }
+        let suffix_count = checked_pow(d, n).context(context)?;
+        let n_nodes = suffix_count
+            .checked_mul(d + 1)
+            .ok_or_else(|| anyhow!("too many nodes"))
+            .context(context)?;
+        let n_edges = checked_n_edges(n_nodes, d).context(context)?;
+        Ok(Box::new(move |_| {
+            let mut g = Graph::with_capacity(n_nodes, n_edges);
+            (0..n_nodes).for_each(|_| g.new_node());
+            for label in 0..n_nodes {
+                let word = kautz_word(label, d, n, suffix_count);
+                let mut next = word[1..].to_vec();
+                next.push(0);
+                for a in (0..=d).filter(|a| *a != word[n]) {
+                    next[n] = a;
+                    g.new_edge(label, kautz_label(&next, d));
+                }
+            }
+            g
+        }))
+    }
+}
+
+impl<R> GeneratorFactory<Directed, R> for KautzGeneratorFactory where R: Rng {}
+
+fn checked_pow(d: usize, n: usize) -> Result<usize> {
+    u32::try_from(n)
+        .ok()
+        .and_then(|n| d.checked_pow(n))
+        .ok_or_else(|| anyhow!("too many nodes"))
+}
+
+fn checked_n_edges(n_nodes: usize, d: usize) -> Result<usize> {
+    n_nodes
+        .checked_mul(d)
+        .ok_or_else(|| anyhow!("too many edges"))
+}
+
+/// Returns the Kautz word of length `n+1` with the given label.
+///
+/// Each symbol after the first one is encoded by its rank among the `d` symbols that differ from the previous one.
+fn kautz_word(label: usize, d: usize, n: usize, suffix_count: usize) -> Vec<usize> {
+    let mut word = Vec::with_capacity(n + 1);
+    word.push(label / suffix_count);
+    let mut rest = label % suffix_count;
+    let mut weight = suffix_count;
+    for _ in 0..n {
+        weight /= d;
+        let rank = rest / weight;
+        rest %= weight;
+        let previous = word[word.len() - 1];
+        word.push(if rank < previous { rank } else { rank + 1 });
+    }
+    word
+}
+
+/// Returns the label of a Kautz word; this is the reverse function of [`kautz_word`].
+fn kautz_label(word: &[usize], d: usize) -> usize {
+    word.windows(2).fold(word[0], |label, pair| {
+        let rank = if pair[1] < pair[0] {
+            pair[1]
+        } else {
+            pair[1] - 1
+        };
+        label * d + rank
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+    use rand::rngs::ThreadRng;
+
+    fn params(d: usize, n: usize) -> Vec<ParameterValue> {
+        vec![
+            ParameterValue::PositiveInteger(d),
+            ParameterValue::PositiveInteger(n),
+        ]
+    }
+
+    fn sorted_edges(g: &Graph<Directed>) -> Vec<(NodeIndexType, NodeIndexType)> {
+        let mut edges = g
+            .iter_edges()
+            .collect::<Vec<(NodeIndexType, NodeIndexType)>>();
+        edges.sort_unstable();
+        edges
+    }
+
+    #[test]
+    fn test_zero_parameters() {
+        assert!((DeBruijnGeneratorFactory.try_with_params(params(2, 0))
+            as Result<BoxedGenerator<Directed, ThreadRng>>)
+            .is_err());
+        assert!((KautzGeneratorFactory.try_with_params(params(0, 2))
+            as Result<BoxedGenerator<Directed, ThreadRng>>)
+            .is_err());
+    }
+
+    #[test]
+    fn test_too_many_edges() {
+        assert!(
+            (DeBruijnGeneratorFactory.try_with_params(params(1 << 33, 1))
+                as Result<BoxedGenerator<Directed, ThreadRng>>)
+                .is_err()
+        );
+        assert!((KautzGeneratorFactory.try_with_params(params(1 << 31, 1))
+            as Result<BoxedGenerator<Directed, ThreadRng>>)
+            .is_err());
+    }
+
+    #[test]
+    fn test_de_bruijn() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = DeBruijnGeneratorFactory
+            .try_with_params(params(2, 2))
+            .unwrap()(&mut rng);
+        assert_eq!(4, g.n_nodes());
+        assert_eq!(
+            vec![
+                (0, 0),
+                (0, 1),
+                (1, 2),
+                (1, 3),
+                (2, 0),
+                (2, 1),
+                (3, 2),
+                (3, 3)
+            ],
+            sorted_edges(&g)
+        );
+    }
+
+    #[test]
+    fn test_kautz_words() {
+        let (d, n) = (3, 3);
+        let suffix_count = 27;
+        for label in 0..(d + 1) * suffix_count {
+            let word = kautz_word(label, d, n, suffix_count);
+            assert!(word.windows(2).all(|pair| pair[0] != pair[1]));
+            assert_eq!(label, kautz_label(&word, d));
+        }
+    }
+
+    #[test]
+    fn test_kautz() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> =
+            KautzGeneratorFactory.try_with_params(params(2, 1)).unwrap()(&mut rng);
+        assert_eq!(6, g.n_nodes());
+        // words are 01, 02, 10, 12, 20, 21
+        assert_eq!(
+            vec![
+                (0, 2),
+                (0, 3),
+                (1, 4),
+                (1, 5),
+                (2, 0),
+                (2, 1),
+                (3, 4),
+                (3, 5),
+                (4, 0),
+                (4, 1),
+                (5, 2),
+                (5, 3)
+            ],
+            sorted_edges(&g)
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use anyhow::{anyhow, Context, Result};
+use rand::{
+    distributions::{Uniform, WeightedIndex},
+    prelude::Distribution,
+    Rng,
+};
+use rand_distr::{Poisson, Zipf};
+use std::{fs, str::FromStr};
+
+/// A distribution used to draw the degrees of the nodes of a graph.
+#[derive(Clone, Copy)]
+pub(crate) enum DegreeDistribution {
+    /// A power law with the given exponent, restricted to degrees between 1 and `n-1`
+    PowerLaw(f64),
+    /// A power law with the given exponent, restricted to degrees between the given bounds (inclusive)
+    BoundedPowerLaw(f64, usize, usize),
+    /// A Poisson distribution with the given mean
+    Poisson(f64),
+    /// A uniform distribution over the given (inclusive) range
+    Uniform(usize, usize),
+}
+
+impl DegreeDistribution {
+    /// Checks the distribution is able to produce a sequence of `n` degrees which sum is even.
+    pub(crate) fn check_even_sum(&self, n: usize) -> Result<()> {
+        match self {
+            DegreeDistribution::Uniform(min, max) if min == max && min & n & 1 == 1 => Err(
+                anyhow!("an odd number of nodes with the same odd degree cannot have an even degree sum"),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Builds a sampler drawing the degrees of the nodes of a graph with `n` nodes.
+    ///
+    /// The bounds of uniform distributions must be ordered.
+    pub(crate) fn sampler(&self, n: usize) -> Result<DegreeSampler> {
+        match *self {
+            DegreeDistribution::PowerLaw(exponent) => {
+                if n < 2 {
+                    Ok(DegreeSampler::Constant(0))
+                } else {
+                    Ok(DegreeSampler::Zipf(
+                        Zipf::new(n as u64 - 1, exponent)
+                            .map_err(|e| anyhow!("{}", e))
+                            .context("while building a power law distribution")?,
+                    ))
+                }
+            }
+            DegreeDistribution::BoundedPowerLaw(exponent, min, max) => {
+                let weights = WeightedIndex::new((min..=max).map(|k| (k as f64).powf(-exponent)))
+                    .context("while building a power law distribution")?;
+                Ok(DegreeSampler::Weighted(min, weights))
+            }
+            DegreeDistribution::Poisson(mean) => {
+                if mean == 0. {
+                    Ok(DegreeSampler::Constant(0))
+                } else {
+                    Ok(DegreeSampler::Poisson(
+                        Poisson::new(mean)
+                            .map_err(|e| anyhow!("{}", e))
+                            .context("while building a Poisson distribution")?,
+                    ))
+                }
+            }
+            DegreeDistribution::Uniform(min, max) => {
+                Ok(DegreeSampler::Uniform(Uniform::new_inclusive(min, max)))
+            }
+        }
+    }
+}
+
+/// A sampler built from a [`DegreeDistribution`] for a given number of nodes.
+#[derive(Clone)]
+pub(crate) enum DegreeSampler {
+    Constant(usize),
+    Zipf(Zipf<f64>),
+    Weighted(usize, WeightedIndex<f64>),
+    Poisson(Poisson<f64>),
+    Uniform(Uniform<usize>),
+}
+
+impl DegreeSampler {
+    /// Draws a sequence of `n` degrees.
+    ///
+    /// If `even_sum` is `true`, random degrees are drawn again until their sum is even.
+    /// In this case, [`DegreeDistribution::check_even_sum`] must have succeeded before the call to this function.
+    pub(crate) fn sample_sequence<R>(&self, n: usize, even_sum: bool, rng: &mut R) -> Vec<usize>
+    where
+        R: Rng,
+    {
+        let mut sequence: Vec<usize> = (0..n).map(|_| self.sample(rng)).collect();
+        if even_sum && n > 0 {
+            let index_uniform = Uniform::new(0, n);
+            while sequence.iter().sum::<usize>() & 1 == 1 {
+                sequence[index_uniform.sample(rng)] = self.sample(rng);
+            }
+        }
+        sequence
+    }
+}
+
+impl Distribution<usize> for DegreeSampler {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        match self {
+            DegreeSampler::Constant(degree) => *degree,
+            DegreeSampler::Zipf(zipf) => zipf.sample(rng) as usize,
+            DegreeSampler::Weighted(min, weights) => min + weights.sample(rng),
+            DegreeSampler::Poisson(poisson) => poisson.sample(rng) as usize,
+            DegreeSampler::Uniform(uniform) => uniform.sample(rng),
+        }
+    }
+}
+
+/// Reads `n_columns` columns of values from a file.
+///
+/// Each non-empty line of the file must contain exactly `n_columns` values, separated by whitespaces.
+/// Lines beginning with a `#` are considered as comments and are ignored.
+/// The values of the i-th line are stored at the i-th position of each column.
+pub(crate) fn read_columns<T>(path: &str, n_columns: usize) -> Result<Vec<Vec<T>>>
+where
+    T: FromStr,
+    <T as FromStr>::Err: std::error::Error + Send + Sync + 'static,
+{
+    let context = || format!(r#"while reading values from file "{}""#, path);
+    let content = fs::read_to_string(path).with_context(context)?;
+    let mut columns: Vec<Vec<T>> = (0..n_columns).map(|_| vec![]).collect();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let words = line.split_whitespace().collect::<Vec<&str>>();
+        if words.len() != n_columns {
+            return Err(anyhow!(
+                "expected {} values at line {}, got {}",
+                n_columns,
+                i + 1,
+                words.len()
+            ))
+            .with_context(context);
+        }
+        for (column, word) in columns.iter_mut().zip(words) {
+            column.push(
+                str::parse(word)
+                    .with_context(|| format!(r#"while parsing "{}" at line {}"#, word, i + 1))
+                    .with_context(context)?,
+            );
+        }
+    }
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uniform_even_sum() {
+        let mut rng = rand::thread_rng();
+        let sequence = DegreeDistribution::Uniform(1, 3)
+            .sampler(5)
+            .unwrap()
+            .sample_sequence(5, true, &mut rng);
+        assert_eq!(5, sequence.len());
+        assert!(sequence.iter().all(|d| (1..=3).contains(d)));
+        assert_eq!(0, sequence.iter().sum::<usize>() & 1);
+    }
+
+    #[test]
+    fn test_power_law_bounds() {
+        let mut rng = rand::thread_rng();
+        let sequence = DegreeDistribution::PowerLaw(2.5)
+            .sampler(10)
+            .unwrap()
+            .sample_sequence(10, false, &mut rng);
+        assert!(sequence.iter().all(|d| (1..=9).contains(d)));
+    }
+
+    #[test]
+    fn test_bounded_power_law_bounds() {
+        let mut rng = rand::thread_rng();
+        let sequence = DegreeDistribution::BoundedPowerLaw(2.5, 3, 5)
+            .sampler(10)
+            .unwrap()
+            .sample_sequence(100, false, &mut rng);
+        assert!(sequence.iter().all(|d| (3..=5).contains(d)));
+    }
+
+    #[test]
+    fn test_bounded_power_law_extreme_exponent() {
+        assert!(DegreeDistribution::BoundedPowerLaw(2000., 5, 10)
+            .sampler(100)
+            .is_err());
+    }
+
+    #[test]
+    fn test_check_even_sum() {
+        assert!(DegreeDistribution::Uniform(1, 1).check_even_sum(3).is_err());
+        assert!(DegreeDistribution::Uniform(1, 1).check_even_sum(4).is_ok());
+        assert!(DegreeDistribution::Uniform(2, 2).check_even_sum(3).is_ok());
+    }
+
+    #[test]
+    fn test_read_columns_missing_file() {
+        assert!(read_columns::<usize>("/this/file/does/not/exist", 1).is_err());
+    }
+}
@@ -0,0 +1,416 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::Directed;
+use rand::Rng;
+use std::collections::HashSet;
+
+/// A factory used to build generators for graphs following [Price's model](https://en.wikipedia.org/wiki/Price%27s_model).
+///
+/// Nodes are added one at a time, each of them linking to `m` distinct existing nodes (or all of them if there are at most `m`).
+/// The targets are chosen one after the other among the remaining nodes, with a probability proportional to their in-degree plus `a`.
+///
+/// This generator is only available for directed graphs.
+///
+/// Such factories can be created by passing `price/n,m,a` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the number of nodes;
+///   - `m` is the number of outgoing edges of each new node;
+///   - `a` is the initial attractiveness of the nodes (Price used 1).
+///
+/// Parameter `a` must be strictly positive.
+#[derive(Default)]
+pub struct PriceGeneratorFactory;
+
+impl<R> NamedParam<BoxedGenerator<Directed, R>> for PriceGeneratorFactory
+where
+    R: Rng,
+{
+    fn name(&self) -> &'static str {
+        "price"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following Price's model.",
+            "First parameter gives the number of nodes, the second one gives the number of outgoing edges of each new node, and the third one gives the initial attractiveness of the nodes.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveFloat,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Directed, R>> {
+        let context = "while building a Price generator";
+        let n = parameter_values[0].unwrap_usize();
+        let m = parameter_values[1].unwrap_usize();
+        let a = parameter_values[2].unwrap_f64();
+        if a == 0. {
+            return Err(anyhow!(
+                r#"third parameter ("a") must be strictly positive"#
+            ))
+            .context(context);
+        }
+        Ok(Box::new(move |r| {
+            let mut g = Graph::with_capacity(n, n * m);
+            (0..n).for_each(|_| g.new_node());
+            let mut in_degrees = vec![0; n];
+            let mut weights = WeightTree::new(n);
+            let mut chosen = Vec::with_capacity(m);
+            for new_node in 1..n {
+                weights.set(new_node - 1, a);
+                if m >= new_node {
+                    chosen.extend(0..new_node);
+                } else {
+                    while chosen.len() < m {
+                        let target = weights.sample(r);
+                        weights.set(target, 0.);
+                        chosen.push(target);
+                    }
+                }
+                for target in chosen.drain(..) {
+                    g.new_edge(new_node, target);
+                    in_degrees[target] += 1;
+                    weights.set(target, in_degrees[target] as f64 + a);
+                }
+            }
+            g
+        }))
+    }
+}
+
+impl<R> GeneratorFactory<Directed, R> for PriceGeneratorFactory where R: Rng {}
+
+/// A factory used to build generators for graphs following the [directed scale-free model](https://dl.acm.org/doi/10.5555/644108.644133) of Bollobás, Borgs, Chayes and Riordan.
+///
+/// The graph is initialized with a cycle of three nodes.
+/// Then, while the graph has less than `n` nodes, one of the following steps is applied:
+///   - with probability `alpha`, a new node is added with an edge to an existing node chosen according to its in-degree plus `din`;
+///   - with probability `beta`, an edge is added from an existing node chosen according to its out-degree plus `dout`
+///     to an existing node chosen according to its in-degree plus `din`;
+///   - with probability `gamma`, a new node is added with an edge from an existing node chosen according to its out-degree plus `dout`.
+///
+/// Self-loops and multiple edges are discarded.
+///
+/// This generator is only available for directed graphs.
+///
+/// Such factories can be created by passing `bbcr/n,alpha,beta,gamma,din,dout` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the number of nodes;
+///   - `alpha`, `beta` and `gamma` are the probabilities of the three steps;
+///   - `din` and `dout` are the biases added to the in- and out-degrees.
+///
+/// Parameter `n` must be at least 3, `alpha`, `beta` and `gamma` must sum to 1, and `alpha` and `gamma` cannot be both equal to 0.
+/// The reference values given by the authors are `alpha=0.41`, `beta=0.54`, `gamma=0.05`, `din=0.2` and `dout=0`.
+#[derive(Default)]
+pub struct DirectedScaleFreeGeneratorFactory;
+
+impl<R> NamedParam<BoxedGenerator<Directed, R>> for DirectedScaleFreeGeneratorFactory
+where
+    R: Rng,
+{
+    fn name(&self) -> &'static str {
+        "bbcr"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the directed scale-free model of Bollobás, Borgs, Chayes and Riordan.",
+            "First parameter gives the number of nodes, the next three ones give the probabilities of the three steps (alpha, beta, gamma),",
+            "and the last two ones give the biases added to the in- and out-degrees (delta_in, delta_out).",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::Probability,
+            ParameterType::Probability,
+            ParameterType::Probability,
+            ParameterType::PositiveFloat,
+            ParameterType::PositiveFloat,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Directed, R>> {
+        let context = "while building a Bollobás-Borgs-Chayes-Riordan generator";
+        let n = parameter_values[0].unwrap_usize();
+        let alpha = parameter_values[1].unwrap_f64();
+        let beta = parameter_values[2].unwrap_f64();
+        let gamma = parameter_values[3].unwrap_f64();
+        let delta_in = parameter_values[4].unwrap_f64();
+        let delta_out = parameter_values[5].unwrap_f64();
+        if n < 3 {
+            return Err(anyhow!(r#"first parameter ("n") must be at least 3"#)).context(context);
+        }
+        if (alpha + beta + gamma - 1.).abs() > 1e-9 {
+            return Err(anyhow!(
+                r#"step probabilities ("alpha", "beta" and "gamma") must sum to 1"#
+            ))
+            .context(context);
+        }
+        if alpha + gamma == 0. {
+            return Err(anyhow!(
+                r#"step probabilities "alpha" and "gamma" cannot be both equal to 0"#
+            ))
+            .context(context);
+        }
+        Ok(Box::new(move |r| {
+            let mut g = Graph::with_capacity(n, n);
+            (0..n).for_each(|_| g.new_node());
+            let mut edges = HashSet::new();
+            let mut sources = vec![];
+            let mut targets = vec![];
+            let mut n_nodes = 0;
+            while n_nodes < n {
+                let (from, to) = if n_nodes < 3 {
+                    n_nodes += 1;
+                    (n_nodes - 1, n_nodes % 3)
+                } else {
+                    let p: f64 = r.gen();
+                    if p < alpha {
+                        n_nodes += 1;
+                        (
+                            n_nodes - 1,
+                            choose_by_degree(&targets, delta_in, n_nodes - 1, r),
+                        )
+                    } else if p < alpha + beta {
+                        (
+                            choose_by_degree(&sources, delta_out, n_nodes, r),
+                            choose_by_degree(&targets, delta_in, n_nodes, r),
+                        )
+                    } else {
+                        n_nodes += 1;
+                        (
+                            choose_by_degree(&sources, delta_out, n_nodes - 1, r),
+                            n_nodes - 1,
+                        )
+                    }
+                };
+                if from != to && edges.insert((from, to)) {
+                    g.new_edge(from, to);
+                    sources.push(from);
+                    targets.push(to);
+                }
+            }
+            g
+        }))
+    }
+}
+
+impl<R> GeneratorFactory<Directed, R> for DirectedScaleFreeGeneratorFactory where R: Rng {}
+
+/// Chooses a node among the `n_nodes` first ones with a probability proportional to its degree plus `delta`.
+///
+/// The degrees are given by the list of edge endpoints, in which each node appears as many times as its degree.
+fn choose_by_degree<R>(endpoints: &[usize], delta: f64, n_nodes: usize, r: &mut R) -> usize
+where
+    R: Rng,
+{
+    let total = endpoints.len() as f64 + delta * n_nodes as f64;
+    if r.gen::<f64>() * total < endpoints.len() as f64 {
+        endpoints[r.gen_range(0..endpoints.len())]
+    } else {
+        r.gen_range(0..n_nodes)
+    }
+}
+
+/// A [Fenwick tree](https://en.wikipedia.org/wiki/Fenwick_tree) used to choose nodes with a probability proportional to their weights.
+///
+/// Setting the weight of chosen nodes to zero allows to sample nodes without replacement.
+struct WeightTree {
+    weights: Vec<f64>,
+    tree: Vec<f64>,
+    /// The highest index that was given a positive weight.
+    last: usize,
+}
+
+impl WeightTree {
+    fn new(n: usize) -> Self {
+        Self {
+            weights: vec![0.; n],
+            tree: vec![0.; n + 1],
+            last: 0,
+        }
+    }
+
+    fn set(&mut self, index: usize, weight: f64) {
+        let delta = weight - self.weights[index];
+        self.weights[index] = weight;
+        if weight > 0. {
+            self.last = self.last.max(index);
+        }
+        let mut i = index + 1;
+        while i < self.tree.len() {
+            self.tree[i] += delta;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    fn total(&self) -> f64 {
+        let mut i = self.tree.len() - 1;
+        let mut total = 0.;
+        while i > 0 {
+            total += self.tree[i];
+            i -= i & i.wrapping_neg();
+        }
+        total
+    }
+
+    /// Chooses a node with a positive weight; at least one such node must exist.
+    fn sample<R>(&self, r: &mut R) -> usize
+    where
+        R: Rng,
+    {
+        let mut remaining = r.gen::<f64>() * self.total();
+        let mut index = 0;
+        let mut step = (self.tree.len() - 1).next_power_of_two();
+        while step > 0 {
+            if index + step < self.tree.len() && self.tree[index + step] <= remaining {
+                index += step;
+                remaining -= self.tree[index];
+            }
+            step /= 2;
+        }
+        // rounding errors may lead the descent to nodes with a null weight, or past the last node with a positive weight
+        let index = index.min(self.last);
+        (0..=index)
+            .rev()
+            .chain(index + 1..=self.last)
+            .find(|i| self.weights[*i] > 0.)
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+    use rand::rngs::ThreadRng;
+
+    fn bbcr_params(n: usize, alpha: f64, beta: f64, gamma: f64) -> Vec<ParameterValue> {
+        vec![
+            ParameterValue::PositiveInteger(n),
+            ParameterValue::Probability(alpha),
+            ParameterValue::Probability(beta),
+            ParameterValue::Probability(gamma),
+            ParameterValue::PositiveFloat(0.2),
+            ParameterValue::PositiveFloat(0.),
+        ]
+    }
+
+    #[test]
+    fn test_price_attractiveness_is_zero() {
+        assert!((PriceGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(10),
+            ParameterValue::PositiveInteger(2),
+            ParameterValue::PositiveFloat(0.),
+        ]) as Result<BoxedGenerator<Directed, ThreadRng>>)
+            .is_err())
+    }
+
+    #[test]
+    fn test_price() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = PriceGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(100),
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::PositiveFloat(1.),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(100, g.n_nodes());
+        assert_eq!(1 + 2 + 3 * 97, g.n_edges());
+        let mut edges = g
+            .iter_edges()
+            .collect::<Vec<(NodeIndexType, NodeIndexType)>>();
+        assert!(edges.iter().all(|(a, b)| a > b));
+        edges.sort_unstable();
+        edges.dedup();
+        assert_eq!(g.n_edges(), edges.len());
+    }
+
+    #[test]
+    fn test_price_tiny_attractiveness() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = PriceGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(1000),
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::PositiveFloat(1e-9),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(1 + 2 + 3 * 997, g.n_edges());
+        let mut edges = g
+            .iter_edges()
+            .collect::<Vec<(NodeIndexType, NodeIndexType)>>();
+        edges.sort_unstable();
+        edges.dedup();
+        assert_eq!(g.n_edges(), edges.len());
+    }
+
+    #[test]
+    fn test_weight_tree() {
+        let mut rng = rand::thread_rng();
+        let mut weights = WeightTree::new(5);
+        weights.set(1, 2.);
+        weights.set(3, 1e-12);
+        assert!((weights.total() - 2.).abs() < 1e-9);
+        assert!((0..100).all(|_| [1, 3].contains(&weights.sample(&mut rng))));
+        weights.set(1, 0.);
+        assert!((0..100).all(|_| weights.sample(&mut rng) == 3));
+        weights.set(3, 0.);
+        weights.set(0, 0.1);
+        assert!((0..100).all(|_| weights.sample(&mut rng) == 0));
+    }
+
+    #[test]
+    fn test_bbcr_wrong_probabilities() {
+        assert!(
+            (DirectedScaleFreeGeneratorFactory.try_with_params(bbcr_params(10, 0.5, 0.5, 0.5))
+                as Result<BoxedGenerator<Directed, ThreadRng>>)
+                .is_err()
+        );
+        assert!(
+            (DirectedScaleFreeGeneratorFactory.try_with_params(bbcr_params(10, 0., 1., 0.))
+                as Result<BoxedGenerator<Directed, ThreadRng>>)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_bbcr_only_new_nodes() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = DirectedScaleFreeGeneratorFactory
+            .try_with_params(bbcr_params(10, 1., 0., 0.))
+            .unwrap()(&mut rng);
+        assert_eq!(10, g.n_nodes());
+        assert_eq!(10, g.n_edges());
+        assert!(g.iter_edges().skip(3).all(|(a, b)| a > b));
+    }
+
+    #[test]
+    fn test_bbcr() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = DirectedScaleFreeGeneratorFactory
+            .try_with_params(bbcr_params(200, 0.41, 0.54, 0.05))
+            .unwrap()(&mut rng);
+        assert_eq!(200, g.n_nodes());
+        let mut edges = g
+            .iter_edges()
+            .collect::<Vec<(NodeIndexType, NodeIndexType)>>();
+        assert!(edges.iter().all(|(a, b)| a != b));
+        edges.sort_unstable();
+        edges.dedup();
+        assert_eq!(g.n_edges(), edges.len());
+    }
+}
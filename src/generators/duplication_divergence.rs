@@ -0,0 +1,214 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::EdgeType;
+use rand::{
+    distributions::{Distribution, Uniform},
+    Rng,
+};
+
+/// The number of times a parent is drawn for a new node before linking the node to the last drawn parent.
+const N_PARENT_DRAWS: usize = 100;
+
+/// A factory used to build generators for graphs following the [duplication–divergence](https://doi.org/10.1073/pnas.0307252101) model.
+///
+/// The graph is initialized with two linked nodes.
+/// Then, nodes are added one at a time: each new node chooses a parent node uniformly at random,
+/// and is linked to each neighbor of its parent with probability `p`.
+/// If the optional parameter `parent` is set to `true`, the new node is also linked to its parent.
+/// When a new node gets no edge, its parent is chosen again; after 100 unsuccessful draws, the new node is linked to the last drawn parent.
+///
+/// In directed graphs generated by this objects, the out-neighbors and the in-neighbors of the parent are duplicated with the same orientation,
+/// and the link to the parent goes from the new node to it.
+///
+/// Such factories can be created by passing `dd/n,p` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the number of nodes;
+///   - `p` is the probability each edge of the parent is retained.
+///
+/// The optional parameter `parent` (eg. `dd/n,p,parent=true`) defaults to `false`; in this case, `p` must be strictly positive.
+#[derive(Default)]
+pub struct DuplicationDivergenceGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for DuplicationDivergenceGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "dd"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the duplication-divergence model.",
+            "First parameter gives the number of nodes, while the second one gives the probability each edge of the duplicated node is retained.",
+            "New nodes are also linked to the node they duplicate if the optional parameter \"parent\" is set to true.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![ParameterType::PositiveInteger, ParameterType::Probability]
+    }
+
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        vec![(
+            "parent",
+            ParameterType::Boolean,
+            ParameterValue::Boolean(false),
+        )]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a duplication-divergence generator";
+        let n = parameter_values[0].unwrap_usize();
+        let p = parameter_values[1].unwrap_f64();
+        let link_parent = parameter_values[2].unwrap_bool();
+        if p == 0. && !link_parent {
+            return Err(anyhow!(
+                r#"second parameter ("p") must be strictly positive when new nodes are not linked to their parents"#
+            ))
+            .context(context);
+        }
+        Ok(Box::new(move |r| build_graph(n, p, link_parent, r)))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for DuplicationDivergenceGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+fn build_graph<Ty, R>(n: usize, p: f64, link_parent: bool, r: &mut R) -> Graph<Ty>
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    let mut g = Graph::with_capacity(n, 0);
+    (0..n).for_each(|_| g.new_node());
+    if n < 2 {
+        return g;
+    }
+    let mut out_neighbors: Vec<Vec<usize>> = vec![vec![]; n];
+    let mut in_neighbors: Vec<Vec<usize>> = vec![vec![]; n];
+    add_edge(&mut g, &mut out_neighbors, &mut in_neighbors, 0, 1);
+    let proba_uniform = Uniform::new_inclusive(0., 1.);
+    for new_node in 2..n {
+        let mut n_draws = 0;
+        let (targets, sources) = loop {
+            n_draws += 1;
+            let parent = r.gen_range(0..new_node);
+            let mut retained = |neighbors: &[usize]| {
+                neighbors
+                    .iter()
+                    .copied()
+                    .filter(|_| proba_uniform.sample(r) < p)
+                    .collect::<Vec<usize>>()
+            };
+            let mut targets = retained(&out_neighbors[parent]);
+            let sources = if Ty::is_directed() {
+                retained(&in_neighbors[parent])
+            } else {
+                vec![]
+            };
+            if link_parent || n_draws == N_PARENT_DRAWS {
+                targets.push(parent);
+            }
+            if !targets.is_empty() || !sources.is_empty() {
+                break (targets, sources);
+            }
+        };
+        for t in targets {
+            add_edge(&mut g, &mut out_neighbors, &mut in_neighbors, new_node, t);
+        }
+        for s in sources {
+            add_edge(&mut g, &mut out_neighbors, &mut in_neighbors, s, new_node);
+        }
+    }
+    g
+}
+
+fn add_edge<Ty>(
+    g: &mut Graph<Ty>,
+    out_neighbors: &mut [Vec<usize>],
+    in_neighbors: &mut [Vec<usize>],
+    from: usize,
+    to: usize,
+) where
+    Ty: EdgeType,
+{
+    g.new_edge(from, to);
+    out_neighbors[from].push(to);
+    in_neighbors[to].push(from);
+    if !Ty::is_directed() {
+        out_neighbors[to].push(from);
+        in_neighbors[from].push(to);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+    use petgraph::{Directed, Undirected};
+    use rand::rngs::ThreadRng;
+
+    fn params(n: usize, p: f64, parent: bool) -> Vec<ParameterValue> {
+        vec![
+            ParameterValue::PositiveInteger(n),
+            ParameterValue::Probability(p),
+            ParameterValue::Boolean(parent),
+        ]
+    }
+
+    #[test]
+    fn test_no_retention_without_parent() {
+        assert!(
+            (DuplicationDivergenceGeneratorFactory.try_with_params(params(10, 0., false))
+                as Result<BoxedGenerator<Undirected, ThreadRng>>)
+                .is_err()
+        )
+    }
+
+    #[test]
+    fn test_parent_only() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = DuplicationDivergenceGeneratorFactory
+            .try_with_params(params(10, 0., true))
+            .unwrap()(&mut rng);
+        assert_eq!(10, g.n_nodes());
+        assert_eq!(9, g.n_edges());
+        assert!(g.iter_edges().all(|(a, b)| a > b || (a, b) == (0, 1)));
+    }
+
+    #[test]
+    fn test_tiny_retention_without_parent() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = DuplicationDivergenceGeneratorFactory
+            .try_with_params(params(1000, 1e-9, false))
+            .unwrap()(&mut rng);
+        assert_eq!(1000, g.n_nodes());
+        assert!(g.n_edges() >= 999);
+    }
+
+    #[test]
+    fn test_full_retention() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = DuplicationDivergenceGeneratorFactory
+            .try_with_params(params(10, 1., false))
+            .unwrap()(&mut rng);
+        assert_eq!(10, g.n_nodes());
+        let mut edges = g
+            .iter_edges()
+            .collect::<Vec<(NodeIndexType, NodeIndexType)>>();
+        assert!(edges.iter().all(|(a, b)| a != b));
+        edges.sort_unstable();
+        edges.dedup();
+        assert_eq!(g.n_edges(), edges.len());
+        assert!(g.n_edges() >= 9);
+    }
+}
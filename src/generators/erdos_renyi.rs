@@ -1,18 +1,28 @@
 use super::{BoxedGenerator, GeneratorFactory};
-use crate::{NamedParam, ParameterType, ParameterValue};
-use anyhow::Result;
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
 use petgraph::EdgeType;
-use rand::Rng;
+use rand::{
+    distributions::{Distribution, Uniform},
+    Rng,
+};
 
 /// A factory used to build generators for [Erdős–Rényi](https://en.wikipedia.org/wiki/Erd%C5%91s%E2%80%93R%C3%A9nyi_model) graphs.
 ///
 /// In directed graphs generated by this objects, for each pair of nodes, both edges are considered for addition (0, 1 or 2 edges can be generated).
+/// By default, both edges are drawn independently.
+/// When the optional parameter `reciprocal` is set to `true`, the optional parameter `reciprocity` sets instead the expected fraction of edges whose reverse edge is also in the graph,
+/// while keeping the expected number of edges unchanged;
+/// in this case, two nodes are linked in both directions with probability `p*reciprocity`, and in a single direction with probability `2*p*(1-reciprocity)`.
 ///
 /// Such factories can be created by passing `er/n,p` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
 ///   - `n` is the size of graph to produce;
 ///   - `p` is the probability each edge appears in the graph.
 ///
 /// Graphs used for initialization are star graphs.
+/// The optional parameters `reciprocal` and `reciprocity` (eg. `er/n,p,reciprocal=true,reciprocity=0.2`) are only available for directed graphs.
+/// The reciprocity defaults to 0, and cannot be set unless `reciprocal` is `true`; it must be a floating point number between 0 and 1, and `p*(2-reciprocity)` cannot exceed 1.
+///
 /// Parameters must be higher than zero, and `p` must be a floating point number between 0 and 1.
 #[derive(Default)]
 pub struct ErdosRenyiGeneratorFactory;
@@ -37,14 +47,73 @@ where
         vec![ParameterType::PositiveInteger, ParameterType::Probability]
     }
 
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        vec![
+            (
+                "reciprocal",
+                ParameterType::Boolean,
+                ParameterValue::Boolean(false),
+            ),
+            (
+                "reciprocity",
+                ParameterType::Probability,
+                ParameterValue::Probability(0.),
+            ),
+        ]
+    }
+
     fn try_with_params(
         &self,
         parameter_values: Vec<ParameterValue>,
     ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building an Erdős–Rényi generator";
         let n = parameter_values[0].unwrap_usize();
         let p = parameter_values[1].unwrap_f64();
+        let reciprocal = parameter_values[2].unwrap_bool();
+        let reciprocity = parameter_values[3].unwrap_f64();
+        if !reciprocal {
+            if reciprocity > 0. {
+                return Err(anyhow!(
+                    r#"optional parameter "reciprocity" requires "reciprocal" to be set to true"#
+                ))
+                .context(context);
+            }
+            return Ok(Box::new(move |r| {
+                petgraph_gen::random_gnp_graph(r, n, p).into()
+            }));
+        }
+        if !Ty::is_directed() {
+            return Err(anyhow!(
+                r#"optional parameter "reciprocal" is only available for directed graphs"#
+            ))
+            .context(context);
+        }
+        if p * (2. - reciprocity) > 1. {
+            return Err(anyhow!(
+                r#"the probability ("p") is too high for the requested reciprocity"#
+            ))
+            .context(context);
+        }
+        let p_mutual = p * reciprocity;
+        let p_single = 2. * p * (1. - reciprocity);
         Ok(Box::new(move |r| {
-            petgraph_gen::random_gnp_graph(r, n, p).into()
+            let mut g = Graph::with_capacity(n, 0);
+            (0..n).for_each(|_| g.new_node());
+            let proba_uniform = Uniform::new(0., 1.);
+            for i in 0..n {
+                for j in i + 1..n {
+                    let x = proba_uniform.sample(r);
+                    if x < p_mutual {
+                        g.new_edge(i, j);
+                        g.new_edge(j, i);
+                    } else if x < p_mutual + p_single / 2. {
+                        g.new_edge(i, j);
+                    } else if x < p_mutual + p_single {
+                        g.new_edge(j, i);
+                    }
+                }
+            }
+            g
         }))
     }
 }
@@ -56,11 +125,84 @@ where
 {
 }
 
+/// A factory used to build generators for [Erdős–Rényi](https://en.wikipedia.org/wiki/Erd%C5%91s%E2%80%93R%C3%A9nyi_model) graphs with a given number of edges (the `G(n,m)` model).
+///
+/// The edges are chosen uniformly at random among all the possible ones, excluding self-loops.
+///
+/// In directed graphs generated by this objects, the two edges between a pair of nodes are distinct candidates.
+///
+/// Such factories can be created by passing `gnm/n,m` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the size of graph to produce;
+///   - `m` is the number of edges.
+///
+/// Parameter `m` cannot exceed the number of possible edges, that is `n*(n-1)/2` for undirected graphs and `n*(n-1)` for directed ones.
+#[derive(Default)]
+pub struct ErdosRenyiGnmGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for ErdosRenyiGnmGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "gnm"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the G(n,m) Erdős–Rényi model.",
+            "First parameter gives the number of nodes of the graph, while the second one gives its number of edges.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a G(n,m) Erdős–Rényi generator";
+        let n = parameter_values[0].unwrap_usize();
+        let m = parameter_values[1].unwrap_usize();
+        let max_edges = n
+            .checked_mul(n.saturating_sub(1))
+            .map(|max| if Ty::is_directed() { max } else { max / 2 })
+            .unwrap_or(usize::MAX);
+        if m > max_edges {
+            return Err(anyhow!(
+                r#"second parameter ("m") cannot exceed the number of possible edges ({})"#,
+                max_edges
+            ))
+            .context(context);
+        }
+        Ok(Box::new(move |r| {
+            if n == 0 {
+                Graph::default()
+            } else {
+                petgraph_gen::random_gnm_graph(r, n, m).into()
+            }
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for ErdosRenyiGnmGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Graph, NodeIndexType};
-    use petgraph::Directed;
+    use crate::NodeIndexType;
+    use petgraph::{Directed, Undirected};
+    use rand::rngs::ThreadRng;
 
     #[test]
     fn test_probability_0() {
@@ -69,6 +211,8 @@ mod tests {
             .try_with_params(vec![
                 ParameterValue::PositiveInteger(3),
                 ParameterValue::Probability(0.0),
+                ParameterValue::Boolean(false),
+                ParameterValue::Probability(0.),
             ])
             .unwrap()(&mut rng);
         assert_eq!(3, g.n_nodes());
@@ -86,6 +230,8 @@ mod tests {
             .try_with_params(vec![
                 ParameterValue::PositiveInteger(3),
                 ParameterValue::Probability(1.0),
+                ParameterValue::Boolean(false),
+                ParameterValue::Probability(0.),
             ])
             .unwrap()(&mut rng);
         assert_eq!(3, g.n_nodes());
@@ -95,4 +241,94 @@ mod tests {
         edges.sort_unstable();
         assert_eq!(vec![(0, 1), (0, 2), (1, 0), (1, 2), (2, 0), (2, 1)], edges);
     }
+
+    fn reciprocity_params(n: usize, p: f64, reciprocity: f64) -> Vec<ParameterValue> {
+        vec![
+            ParameterValue::PositiveInteger(n),
+            ParameterValue::Probability(p),
+            ParameterValue::Boolean(true),
+            ParameterValue::Probability(reciprocity),
+        ]
+    }
+
+    #[test]
+    fn test_full_reciprocity() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = ErdosRenyiGeneratorFactory
+            .try_with_params(reciprocity_params(50, 0.3, 1.))
+            .unwrap()(&mut rng);
+        let edges = g
+            .iter_edges()
+            .collect::<std::collections::HashSet<(NodeIndexType, NodeIndexType)>>();
+        assert_eq!(g.n_edges(), edges.len());
+        assert!(edges
+            .iter()
+            .all(|(a, b)| a != b && edges.contains(&(*b, *a))));
+    }
+
+    #[test]
+    fn test_no_reciprocity() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = ErdosRenyiGeneratorFactory
+            .try_with_params(reciprocity_params(3, 0.5, 0.))
+            .unwrap()(&mut rng);
+        let edges = g
+            .iter_edges()
+            .collect::<std::collections::HashSet<(NodeIndexType, NodeIndexType)>>();
+        assert!(edges.iter().all(|(a, b)| !edges.contains(&(*b, *a))));
+    }
+
+    #[test]
+    fn test_wrong_reciprocity() {
+        assert!(
+            (ErdosRenyiGeneratorFactory.try_with_params(reciprocity_params(3, 0.6, 0.))
+                as Result<BoxedGenerator<Directed, ThreadRng>>)
+                .is_err()
+        );
+        assert!(
+            crate::generators::directed_generator_factory_from_str("er/3,0.5,reciprocity=2")
+                .is_err()
+        );
+        assert!(
+            crate::generators::directed_generator_factory_from_str("er/3,0.5,reciprocity=0.2")
+                .is_err()
+        );
+        assert!(
+            (ErdosRenyiGeneratorFactory.try_with_params(reciprocity_params(3, 0.5, 0.5))
+                as Result<BoxedGenerator<Undirected, ThreadRng>>)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_gnm() {
+        let mut rng = rand::thread_rng();
+        let params = vec![
+            ParameterValue::PositiveInteger(10),
+            ParameterValue::PositiveInteger(45),
+        ];
+        let g: Graph<Undirected> = ErdosRenyiGnmGeneratorFactory
+            .try_with_params(params.clone())
+            .unwrap()(&mut rng);
+        assert_eq!(10, g.n_nodes());
+        assert_eq!(45, g.n_edges());
+        let g: Graph<Directed> = ErdosRenyiGnmGeneratorFactory
+            .try_with_params(params)
+            .unwrap()(&mut rng);
+        assert_eq!(45, g.n_edges());
+    }
+
+    #[test]
+    fn test_gnm_too_many_edges() {
+        assert!((ErdosRenyiGnmGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(10),
+            ParameterValue::PositiveInteger(46),
+        ]) as Result<BoxedGenerator<Undirected, ThreadRng>>)
+            .is_err());
+        assert!((ErdosRenyiGnmGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(0),
+            ParameterValue::PositiveInteger(1),
+        ]) as Result<BoxedGenerator<Directed, ThreadRng>>)
+            .is_err());
+    }
 }
@@ -0,0 +1,166 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::Directed;
+use rand::{seq::SliceRandom, Rng};
+use rand_distr::{Distribution, Geometric};
+use std::collections::VecDeque;
+
+/// A factory used to build generators for graphs following the [forest fire](https://doi.org/10.1145/1081870.1081893) model.
+///
+/// Nodes are added one at a time.
+/// Each new node links to an ambassador node chosen uniformly at random, and then "burns" through the graph starting from it:
+/// for each burning node, a random number of its out-neighbors and in-neighbors that are not burnt yet are chosen,
+/// linked from the new node and burnt in turn.
+/// These numbers follow geometric distributions with respective means `p/(1-p)` and `q/(1-q)`.
+///
+/// This generator is only available for directed graphs.
+///
+/// Such factories can be created by passing `ff/n,p,q` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the number of nodes;
+///   - `p` is the forward burning probability;
+///   - `q` is the backward burning probability.
+///
+/// Parameters `p` and `q` must be lower than 1.
+#[derive(Default)]
+pub struct ForestFireGeneratorFactory;
+
+impl<R> NamedParam<BoxedGenerator<Directed, R>> for ForestFireGeneratorFactory
+where
+    R: Rng,
+{
+    fn name(&self) -> &'static str {
+        "ff"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the forest fire model.",
+            "First parameter gives the number of nodes, while the second and third ones give the forward and backward burning probabilities.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::Probability,
+            ParameterType::Probability,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Directed, R>> {
+        let context = "while building a forest fire generator";
+        let n = parameter_values[0].unwrap_usize();
+        let p = parameter_values[1].unwrap_f64();
+        let q = parameter_values[2].unwrap_f64();
+        if p >= 1. || q >= 1. {
+            return Err(anyhow!(
+                r#"burning probabilities ("p" and "q") must be lower than 1"#
+            ))
+            .context(context);
+        }
+        let forward = Geometric::new(1. - p).unwrap();
+        let backward = Geometric::new(1. - q).unwrap();
+        Ok(Box::new(move |r| build_graph(n, &forward, &backward, r)))
+    }
+}
+
+impl<R> GeneratorFactory<Directed, R> for ForestFireGeneratorFactory where R: Rng {}
+
+fn build_graph<R>(n: usize, forward: &Geometric, backward: &Geometric, r: &mut R) -> Graph<Directed>
+where
+    R: Rng,
+{
+    let mut g = Graph::with_capacity(n, 0);
+    (0..n).for_each(|_| g.new_node());
+    let mut out_neighbors: Vec<Vec<usize>> = vec![vec![]; n];
+    let mut in_neighbors: Vec<Vec<usize>> = vec![vec![]; n];
+    let mut burnt_by = vec![usize::MAX; n];
+    for new_node in 1..n {
+        burnt_by[new_node] = new_node;
+        let ambassador = r.gen_range(0..new_node);
+        let mut to_visit = VecDeque::from([ambassador]);
+        burnt_by[ambassador] = new_node;
+        let mut targets = vec![ambassador];
+        while let Some(current) = to_visit.pop_front() {
+            for (neighbors, distribution) in [
+                (&out_neighbors[current], forward),
+                (&in_neighbors[current], backward),
+            ] {
+                let mut candidates = neighbors
+                    .iter()
+                    .filter(|i| burnt_by[**i] != new_node)
+                    .copied()
+                    .collect::<Vec<usize>>();
+                let n_burnt = usize::try_from(distribution.sample(r)).unwrap_or(usize::MAX);
+                let (burnt, _) = candidates.partial_shuffle(r, n_burnt);
+                for i in burnt.iter() {
+                    burnt_by[*i] = new_node;
+                    targets.push(*i);
+                    to_visit.push_back(*i);
+                }
+            }
+        }
+        for target in targets {
+            g.new_edge(new_node, target);
+            out_neighbors[new_node].push(target);
+            in_neighbors[target].push(new_node);
+        }
+    }
+    g
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+    use rand::rngs::ThreadRng;
+
+    #[test]
+    fn test_probability_is_one() {
+        assert!((ForestFireGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(10),
+            ParameterValue::Probability(1.),
+            ParameterValue::Probability(0.),
+        ]) as Result<BoxedGenerator<Directed, ThreadRng>>)
+            .is_err())
+    }
+
+    #[test]
+    fn test_no_burning() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = ForestFireGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(10),
+                ParameterValue::Probability(0.),
+                ParameterValue::Probability(0.),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(10, g.n_nodes());
+        assert_eq!(9, g.n_edges());
+        assert!(g.iter_edges().all(|(a, b)| a > b));
+    }
+
+    #[test]
+    fn test_simple_graph() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = ForestFireGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(200),
+                ParameterValue::Probability(0.4),
+                ParameterValue::Probability(0.3),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(200, g.n_nodes());
+        let mut edges = g
+            .iter_edges()
+            .collect::<Vec<(NodeIndexType, NodeIndexType)>>();
+        assert!(edges.iter().all(|(a, b)| a > b));
+        edges.sort_unstable();
+        edges.dedup();
+        assert_eq!(g.n_edges(), edges.len());
+    }
+}
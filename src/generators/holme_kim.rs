@@ -0,0 +1,192 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::EdgeType;
+use rand::{
+    distributions::{Distribution, Uniform},
+    seq::SliceRandom,
+    Rng,
+};
+use std::collections::HashSet;
+
+/// A factory used to build generators for [Holme-Kim](https://arxiv.org/abs/cond-mat/0110452) graphs, also known as power-law cluster graphs.
+///
+/// This model extends the Barabási-Albert one with a triad formation step:
+/// after each preferential attachment edge, the next edge of the new node is set to a random neighbor of the previous target with a given probability,
+/// closing a triangle.
+/// The resulting graphs have power-law degree distributions and high clustering coefficients.
+///
+/// In directed graphs generated with this object, edge sources are the new nodes and targets the existing ones.
+///
+/// Such factories can be created by passing `plc/n,m,p` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the size of graph to produce;
+///   - `m` is the number of edges added with each new node (and the number of initial nodes);
+///   - `p` is the probability to add a triad formation edge instead of a preferential attachment one.
+///
+/// Parameter `m` must be higher than zero, and `n` must be higher than `m`.
+#[derive(Default)]
+pub struct HolmeKimGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for HolmeKimGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "plc"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the Holme-Kim model (power-law cluster graphs).",
+            "First parameter gives the number of nodes of the graph, the second one gives the number of edges added with each new node, and the third one gives the triad formation probability.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+            ParameterType::Probability,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a Holme-Kim generator";
+        let n = parameter_values[0].unwrap_usize();
+        let m = parameter_values[1].unwrap_usize();
+        let p = parameter_values[2].unwrap_f64();
+        if m == 0 || m >= n {
+            return Err(anyhow!(
+                r#"second parameter ("m") must be higher than 0 and lower than the first one ("n")"#
+            ))
+            .context(context);
+        }
+        Ok(Box::new(move |r| build_graph(n, m, p, r)))
+    }
+}
+
+fn build_graph<Ty, R>(n: usize, m: usize, p: f64, r: &mut R) -> Graph<Ty>
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    let mut g = Graph::with_capacity(n, m * (n - m));
+    (0..n).for_each(|_| g.new_node());
+    let mut neighbors: Vec<Vec<usize>> = vec![vec![]; n];
+    let mut repeated_nodes = (0..m).collect::<Vec<usize>>();
+    let proba_uniform = Uniform::new_inclusive(0., 1.);
+    for source in m..n {
+        let mut possible_targets = random_subset(&repeated_nodes, m, r);
+        let mut linked = HashSet::with_capacity(m);
+        let mut target = possible_targets.pop().unwrap();
+        let mut new_targets = Vec::with_capacity(m);
+        loop {
+            linked.insert(target);
+            new_targets.push(target);
+            neighbors[source].push(target);
+            neighbors[target].push(source);
+            g.new_edge(source, target);
+            if new_targets.len() == m {
+                break;
+            }
+            if proba_uniform.sample(r) < p {
+                let neighborhood = neighbors[target]
+                    .iter()
+                    .filter(|nbr| **nbr != source && !linked.contains(*nbr))
+                    .copied()
+                    .collect::<Vec<usize>>();
+                if let Some(nbr) = neighborhood.choose(r) {
+                    target = *nbr;
+                    continue;
+                }
+            }
+            target = loop {
+                let candidate = possible_targets
+                    .pop()
+                    .unwrap_or_else(|| *repeated_nodes.choose(r).unwrap());
+                if !linked.contains(&candidate) {
+                    break candidate;
+                }
+            };
+        }
+        repeated_nodes.append(&mut new_targets);
+        (0..m).for_each(|_| repeated_nodes.push(source));
+    }
+    g
+}
+
+/// Draws `k` distinct elements from the sequence, using the multiplicity of the elements as weights.
+fn random_subset<R>(sequence: &[usize], k: usize, r: &mut R) -> Vec<usize>
+where
+    R: Rng,
+{
+    let mut set = HashSet::with_capacity(k);
+    let mut subset = Vec::with_capacity(k);
+    while subset.len() < k {
+        let x = *sequence.choose(r).unwrap();
+        if set.insert(x) {
+            subset.push(x);
+        }
+    }
+    subset
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for HolmeKimGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use petgraph::{Directed, Undirected};
+    use rand::rngs::ThreadRng;
+
+    #[test]
+    fn test_m_is_zero() {
+        assert!((HolmeKimGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(2),
+            ParameterValue::PositiveInteger(0),
+            ParameterValue::Probability(0.5),
+        ]) as Result<BoxedGenerator<Directed, ThreadRng>>)
+            .is_err())
+    }
+
+    #[test]
+    fn test_m_is_not_lower_than_n() {
+        assert!((HolmeKimGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(2),
+            ParameterValue::PositiveInteger(2),
+            ParameterValue::Probability(0.5),
+        ]) as Result<BoxedGenerator<Directed, ThreadRng>>)
+            .is_err())
+    }
+
+    #[test]
+    fn test_simple_graph() {
+        let mut rng = rand::thread_rng();
+        for p in [0.0, 0.5, 1.0] {
+            let g: Graph<Undirected> = HolmeKimGeneratorFactory
+                .try_with_params(vec![
+                    ParameterValue::PositiveInteger(50),
+                    ParameterValue::PositiveInteger(3),
+                    ParameterValue::Probability(p),
+                ])
+                .unwrap()(&mut rng);
+            assert_eq!(50, g.n_nodes());
+            assert_eq!(3 * 47, g.n_edges());
+            let edges = g
+                .iter_edges()
+                .map(|(a, b)| (usize::min(a, b), usize::max(a, b)))
+                .collect::<HashSet<(usize, usize)>>();
+            assert_eq!(3 * 47, edges.len());
+            assert!(edges.iter().all(|(a, b)| a != b));
+        }
+    }
+}
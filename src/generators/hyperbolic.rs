@@ -0,0 +1,366 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::EdgeType;
+use rand::Rng;
+use rand_distr::{Distribution, Geometric};
+use std::f64::consts::PI;
+
+/// A factory used to build generators for [hyperbolic random graphs](https://doi.org/10.1103/PhysRevE.82.036106).
+///
+/// Nodes are placed in a hyperbolic disk of radius `R`, with angles drawn uniformly and radii drawn so that the degrees follow a power law of exponent `gamma`.
+/// Two nodes at hyperbolic distance `d` are linked with probability `1/(1+exp((d-R)/(2*t)))`, or if and only if `d<=R` when the temperature `t` is 0.
+/// The radius `R` is set so that the expected average degree is asymptotically equal to `k`.
+///
+/// The sampling algorithm splits the disk into concentric bands in which nodes are sorted by their angles,
+/// and skips over unlikely candidates using an upper bound of their connection probability, avoiding to consider all the pairs of nodes.
+///
+/// The Cartesian coordinates of the nodes in the native representation of the disk are available through [`Graph::positions`].
+///
+/// In directed graphs generated by this objects, edges are set in both directions.
+///
+/// Such factories can be created by passing `hrg/n,k,gamma,t` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the number of nodes;
+///   - `k` is the expected average degree;
+///   - `gamma` is the exponent of the degree distribution;
+///   - `t` is the temperature.
+///
+/// Parameter `k` must be strictly positive and lower than `n`, `gamma` must be higher than 2, and `t` must be lower than 1.
+#[derive(Default)]
+pub struct HyperbolicGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for HyperbolicGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "hrg"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator of hyperbolic random graphs.",
+            "First parameter gives the number of nodes, the second one gives the expected average degree,",
+            "the third one gives the exponent of the degree distribution, and the fourth one gives the temperature.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveFloat,
+            ParameterType::PositiveFloat,
+            ParameterType::Probability,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a hyperbolic random graph generator";
+        let n = parameter_values[0].unwrap_usize();
+        let k = parameter_values[1].unwrap_f64();
+        let gamma = parameter_values[2].unwrap_f64();
+        let t = parameter_values[3].unwrap_f64();
+        if k == 0. || k >= n as f64 {
+            return Err(anyhow!(
+                r#"second parameter ("k") must be strictly positive and lower than the first one ("n")"#
+            ))
+            .context(context);
+        }
+        if gamma <= 2. {
+            return Err(anyhow!(
+                r#"third parameter ("gamma") must be higher than 2"#
+            ))
+            .context(context);
+        }
+        if t >= 1. {
+            return Err(anyhow!(r#"fourth parameter ("t") must be lower than 1"#)).context(context);
+        }
+        let disk = HyperbolicDisk::new(n, k, gamma, t);
+        Ok(Box::new(move |r| disk.build_graph(n, r)))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for HyperbolicGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+struct HyperbolicDisk {
+    radius: f64,
+    alpha: f64,
+    temperature: f64,
+}
+
+impl HyperbolicDisk {
+    fn new(n: usize, k: f64, gamma: f64, temperature: f64) -> Self {
+        let alpha = (gamma - 1.) / 2.;
+        let angular_factor = if temperature == 0. {
+            PI
+        } else {
+            (PI * temperature).sin() / temperature
+        };
+        let radius = 2.
+            * (2. * alpha * alpha * n as f64
+                / (angular_factor * k * (alpha - 0.5) * (alpha - 0.5)))
+                .ln();
+        Self {
+            radius: f64::max(radius, 0.),
+            alpha,
+            temperature,
+        }
+    }
+
+    fn sample_radius<R>(&self, r: &mut R) -> f64
+    where
+        R: Rng,
+    {
+        let u: f64 = r.gen();
+        (1. + u * ((self.alpha * self.radius).cosh() - 1.)).acosh() / self.alpha
+    }
+
+    fn connection_probability(&self, d: f64) -> f64 {
+        if self.temperature == 0. {
+            if d <= self.radius {
+                1.
+            } else {
+                0.
+            }
+        } else {
+            1. / (1. + ((d - self.radius) / (2. * self.temperature)).exp())
+        }
+    }
+
+    fn build_graph<Ty, R>(&self, n: usize, r: &mut R) -> Graph<Ty>
+    where
+        R: Rng,
+        Ty: EdgeType,
+    {
+        let radii = (0..n).map(|_| self.sample_radius(r)).collect::<Vec<f64>>();
+        let angles = (0..n)
+            .map(|_| r.gen_range(0. ..2. * PI))
+            .collect::<Vec<f64>>();
+        let n_bands = usize::max(1, (n as f64).log2().ceil() as usize);
+        let band_width = self.radius / n_bands as f64;
+        let band_of = |radius: f64| {
+            if band_width == 0. {
+                0
+            } else {
+                usize::min((radius / band_width) as usize, n_bands - 1)
+            }
+        };
+        let mut bands = vec![vec![]; n_bands];
+        (0..n).for_each(|i| bands[band_of(radii[i])].push(i));
+        bands
+            .iter_mut()
+            .for_each(|b| b.sort_unstable_by(|i, j| angles[*i].total_cmp(&angles[*j])));
+        let mut g = Graph::with_capacity(n, 0);
+        (0..n).for_each(|_| g.new_node());
+        for u in 0..n {
+            let band_u = band_of(radii[u]);
+            for (b, band) in bands.iter().enumerate().skip(band_u) {
+                let band_bounds = (
+                    b as f64 * band_width,
+                    if b == n_bands - 1 {
+                        self.radius
+                    } else {
+                        (b + 1) as f64 * band_width
+                    },
+                );
+                let delta_to = |v: usize| {
+                    let offset = (angles[v] - angles[u]).rem_euclid(2. * PI);
+                    f64::min(offset, 2. * PI - offset)
+                };
+                let len = band.len();
+                let start = band.partition_point(|v| angles[*v] < angles[u]);
+                let n_forward = partition_point(len, |i| {
+                    (angles[band[(start + i) % len]] - angles[u]).rem_euclid(2. * PI) < PI
+                });
+                let forward = |i: usize| band[(start + i) % len];
+                let backward = |i: usize| band[(start + len - 1 - i) % len];
+                for (count, item) in [
+                    (n_forward, &forward as &dyn Fn(usize) -> usize),
+                    (len - n_forward, &backward),
+                ] {
+                    let mut chunk_start = 0;
+                    let mut chunk_len = 1;
+                    while chunk_start < count {
+                        let chunk_end = usize::min(chunk_start + chunk_len, count);
+                        let min_distance = min_distance_to_band(
+                            radii[u],
+                            band_bounds,
+                            delta_to(item(chunk_start)),
+                        );
+                        let max_proba = self.connection_probability(min_distance);
+                        if max_proba == 0. {
+                            if self.temperature == 0. {
+                                break;
+                            }
+                        } else {
+                            let skips = Geometric::new(f64::min(max_proba, 1.)).unwrap();
+                            let mut i = chunk_start;
+                            loop {
+                                let skip = usize::try_from(skips.sample(r)).unwrap_or(usize::MAX);
+                                i = i.saturating_add(skip);
+                                if i >= chunk_end {
+                                    break;
+                                }
+                                let v = item(i);
+                                i += 1;
+                                if v == u || (b == band_u && v < u) {
+                                    continue;
+                                }
+                                let d = hyperbolic_distance(radii[u], radii[v], delta_to(v));
+                                if r.gen::<f64>() * max_proba < self.connection_probability(d) {
+                                    g.new_edge(u, v);
+                                    if Ty::is_directed() {
+                                        g.new_edge(v, u);
+                                    }
+                                }
+                            }
+                        }
+                        chunk_start = chunk_end;
+                        chunk_len *= 2;
+                    }
+                }
+            }
+        }
+        g.set_positions(
+            (0..n)
+                .map(|i| vec![radii[i] * angles[i].cos(), radii[i] * angles[i].sin()])
+                .collect(),
+        );
+        g
+    }
+}
+
+/// Returns the first index in `0..len` for which the predicate does not hold, assuming it holds for a prefix of this range.
+fn partition_point<F>(len: usize, pred: F) -> usize
+where
+    F: Fn(usize) -> bool,
+{
+    let (mut low, mut high) = (0, len);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if pred(mid) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+fn hyperbolic_distance(r1: f64, r2: f64, delta: f64) -> f64 {
+    let half_delta_sin = (delta / 2.).sin();
+    ((r1 - r2).cosh() + 2. * half_delta_sin * half_delta_sin * r1.sinh() * r2.sinh()).acosh()
+}
+
+/// Returns the minimal distance between a node at radius `r` and the nodes at an angular distance of `delta` in the given band.
+fn min_distance_to_band(r: f64, (low, high): (f64, f64), delta: f64) -> f64 {
+    let closest = (r.tanh() * delta.cos()).max(0.).atanh();
+    hyperbolic_distance(r, closest.clamp(low, high), delta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use petgraph::{Directed, Undirected};
+    use rand::rngs::ThreadRng;
+    use std::collections::HashSet;
+
+    fn params(n: usize, k: f64, gamma: f64, t: f64) -> Vec<ParameterValue> {
+        vec![
+            ParameterValue::PositiveInteger(n),
+            ParameterValue::PositiveFloat(k),
+            ParameterValue::PositiveFloat(gamma),
+            ParameterValue::Probability(t),
+        ]
+    }
+
+    type Pair = (usize, usize);
+
+    /// Returns the pairs of nodes which distance is lower than the radius, and the ones which distance is close to it.
+    fn naive_edges(g: &Graph<Undirected>, disk: &HyperbolicDisk) -> (HashSet<Pair>, HashSet<Pair>) {
+        let positions = g.positions().unwrap();
+        let polar = positions
+            .iter()
+            .map(|p| (f64::hypot(p[0], p[1]), p[1].atan2(p[0])))
+            .collect::<Vec<(f64, f64)>>();
+        let mut edges = HashSet::new();
+        let mut borderline = HashSet::new();
+        for i in 0..polar.len() {
+            for j in i + 1..polar.len() {
+                let offset = (polar[i].1 - polar[j].1).rem_euclid(2. * PI);
+                let delta = f64::min(offset, 2. * PI - offset);
+                let distance = hyperbolic_distance(polar[i].0, polar[j].0, delta);
+                if (distance - disk.radius).abs() <= 1e-6 {
+                    borderline.insert((i, j));
+                } else if distance < disk.radius {
+                    edges.insert((i, j));
+                }
+            }
+        }
+        (edges, borderline)
+    }
+
+    #[test]
+    fn test_wrong_params() {
+        for p in [
+            params(10, 0., 3., 0.),
+            params(10, 10., 3., 0.),
+            params(10, 2., 2., 0.),
+            params(10, 2., 3., 1.),
+        ] {
+            assert!((HyperbolicGeneratorFactory.try_with_params(p)
+                as Result<BoxedGenerator<Undirected, ThreadRng>>)
+                .is_err())
+        }
+    }
+
+    #[test]
+    fn test_threshold_model_matches_naive() {
+        let mut rng = rand::thread_rng();
+        let disk = HyperbolicDisk::new(300, 8., 2.5, 0.);
+        let g: Graph<Undirected> = disk.build_graph(300, &mut rng);
+        assert_eq!(300, g.n_nodes());
+        let edges = g
+            .iter_edges()
+            .map(|(a, b)| (usize::min(a, b), usize::max(a, b)))
+            .collect::<HashSet<(usize, usize)>>();
+        assert_eq!(g.n_edges(), edges.len());
+        let (expected, borderline) = naive_edges(&g, &disk);
+        assert!(expected.iter().all(|e| edges.contains(e)));
+        assert!(edges
+            .iter()
+            .all(|e| expected.contains(e) || borderline.contains(e)));
+    }
+
+    #[test]
+    fn test_average_degree() {
+        let mut rng = rand::thread_rng();
+        for t in [0., 0.5] {
+            let g: Graph<Undirected> = HyperbolicGeneratorFactory
+                .try_with_params(params(2000, 10., 3., t))
+                .unwrap()(&mut rng);
+            let average_degree = 2. * g.n_edges() as f64 / 2000.;
+            assert!((4. ..25.).contains(&average_degree), "{average_degree}");
+        }
+    }
+
+    #[test]
+    fn test_directed() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = HyperbolicGeneratorFactory
+            .try_with_params(params(200, 5., 3., 0.3))
+            .unwrap()(&mut rng);
+        assert_eq!(200, g.n_nodes());
+        assert_eq!(0, g.n_edges() % 2);
+        assert!(g.iter_edges().all(|(a, b)| a != b));
+    }
+}
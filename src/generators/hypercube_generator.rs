@@ -0,0 +1,110 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::EdgeType;
+use rand::Rng;
+
+/// A factory used to build generators for [hypercube graphs](https://en.wikipedia.org/wiki/Hypercube_graph).
+///
+/// The hypercube of dimension `d` has `2^d` nodes, and two nodes are linked if and only if the binary representations of their labels differ by exactly one bit.
+///
+/// In directed graphs generated by this objects, each pair of linked nodes gets two edges, one in each direction.
+///
+/// Such factories can be created by passing `hypercube/d` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str),
+/// where `d` is the dimension of the hypercube.
+#[derive(Default)]
+pub struct HypercubeGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for HypercubeGeneratorFactory
+where
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "hypercube"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a hypercube graph.",
+            "The first parameter gives the dimension of the hypercube.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![ParameterType::PositiveInteger]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a hypercube generator";
+        let d = parameter_values[0].unwrap_usize();
+        let n = u32::try_from(d)
+            .ok()
+            .and_then(|d| 1_usize.checked_shl(d))
+            .ok_or_else(|| anyhow!("too many nodes"))
+            .context(context)?;
+        Ok(Box::new(move |_| {
+            let mut g = Graph::with_capacity(n, n * d);
+            (0..n).for_each(|_| g.new_node());
+            for i in 0..n {
+                for bit in (0..d).map(|b| 1 << b).filter(|bit| i & bit == 0) {
+                    g.new_edge(i, i | bit);
+                    if Ty::is_directed() {
+                        g.new_edge(i | bit, i);
+                    }
+                }
+            }
+            g
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for HypercubeGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+    use petgraph::{Directed, Undirected};
+    use rand::rngs::ThreadRng;
+
+    #[test]
+    fn test_dimension_too_high() {
+        assert!((HypercubeGeneratorFactory
+            .try_with_params(vec![ParameterValue::PositiveInteger(usize::BITS as usize)])
+            as Result<BoxedGenerator<Undirected, ThreadRng>>)
+            .is_err())
+    }
+
+    #[test]
+    fn test_square() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = HypercubeGeneratorFactory
+            .try_with_params(vec![ParameterValue::PositiveInteger(2)])
+            .unwrap()(&mut rng);
+        assert_eq!(4, g.n_nodes());
+        assert_eq!(
+            vec![(0, 1), (0, 2), (1, 3), (2, 3)],
+            g.iter_edges()
+                .collect::<Vec<(NodeIndexType, NodeIndexType)>>()
+        );
+    }
+
+    #[test]
+    fn test_directed_cube() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = HypercubeGeneratorFactory
+            .try_with_params(vec![ParameterValue::PositiveInteger(3)])
+            .unwrap()(&mut rng);
+        assert_eq!(8, g.n_nodes());
+        assert_eq!(24, g.n_edges());
+        assert!(g.iter_edges().all(|(a, b)| (a ^ b).count_ones() == 1));
+    }
+}
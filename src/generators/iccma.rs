@@ -0,0 +1,497 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::Directed;
+use rand::{
+    distributions::{Distribution, Uniform},
+    seq::{index, SliceRandom},
+    Rng,
+};
+use std::collections::HashSet;
+
+/// A factory used to build generators for argumentation frameworks following the GroundedGenerator family of the [ICCMA 2015](https://argumentationcompetition.org/2015/) benchmarks.
+///
+/// Each attack from an argument `i` to an argument `j` such that `i<j` is added with probability `p`.
+/// The resulting frameworks are acyclic, so their grounded extension is also their unique stable extension;
+/// this extension is usually large.
+///
+/// This generator is only available for directed graphs.
+///
+/// Such factories can be created by passing `iccma_grounded/n,p` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the number of arguments;
+///   - `p` is the probability of each attack.
+#[derive(Default)]
+pub struct IccmaGroundedGeneratorFactory;
+
+impl<R> NamedParam<BoxedGenerator<Directed, R>> for IccmaGroundedGeneratorFactory
+where
+    R: Rng,
+{
+    fn name(&self) -> &'static str {
+        "iccma_grounded"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator of acyclic argumentation frameworks following the ICCMA 2015 GroundedGenerator.",
+            "First parameter gives the number of arguments, while the second one gives the probability of each attack.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![ParameterType::PositiveInteger, ParameterType::Probability]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Directed, R>> {
+        let n = parameter_values[0].unwrap_usize();
+        let p = parameter_values[1].unwrap_f64();
+        Ok(Box::new(move |r| {
+            let mut g = Graph::with_capacity(n, 0);
+            (0..n).for_each(|_| g.new_node());
+            let proba_uniform = Uniform::new_inclusive(0., 1.);
+            for i in 0..n {
+                for j in i + 1..n {
+                    if proba_uniform.sample(r) < p {
+                        g.new_edge(i, j);
+                    }
+                }
+            }
+            g
+        }))
+    }
+}
+
+impl<R> GeneratorFactory<Directed, R> for IccmaGroundedGeneratorFactory where R: Rng {}
+
+/// A factory used to build generators for argumentation frameworks following the StableGenerator family of the [ICCMA 2015](https://argumentationcompetition.org/2015/) benchmarks.
+///
+/// The generation process plants several stable extensions sharing a common grounded part:
+///   - a set of unattacked arguments is chosen to be the grounded extension;
+///   - a given number of extensions are built by adding a random set of other arguments to the grounded extension;
+///   - for each extension, each argument it does not contain is attacked by a random argument of the extension,
+///     provided that both arguments do not appear together in another extension.
+///
+/// When all these attacks can be added (which is the case in particular when no argument is shared between the planted extensions),
+/// each planted extension is a stable extension of the framework.
+///
+/// This generator is only available for directed graphs.
+///
+/// Such factories can be created by passing `iccma_stable/n,emin,emax,smin,smax,gmin,gmax` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the number of arguments;
+///   - `emin` and `emax` are the minimal and maximal numbers of planted extensions;
+///   - `smin` and `smax` are the minimal and maximal sizes of the extensions, grounded arguments excluded;
+///   - `gmin` and `gmax` are the minimal and maximal sizes of the grounded extension.
+///
+/// Each minimal value must not be higher than the related maximal one, and the sum of `smax` and `gmax` must not exceed `n`.
+#[derive(Default)]
+pub struct IccmaStableGeneratorFactory;
+
+impl<R> NamedParam<BoxedGenerator<Directed, R>> for IccmaStableGeneratorFactory
+where
+    R: Rng,
+{
+    fn name(&self) -> &'static str {
+        "iccma_stable"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator of argumentation frameworks with planted stable extensions following the ICCMA 2015 StableGenerator.",
+            "First parameter gives the number of arguments, and the next ones give the minimal and maximal numbers of extensions,",
+            "the minimal and maximal sizes of the extensions (grounded arguments excluded), and the minimal and maximal sizes of the grounded extension.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        (0..7).map(|_| ParameterType::PositiveInteger).collect()
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Directed, R>> {
+        let context = "while building an ICCMA stable generator";
+        let n = parameter_values[0].unwrap_usize();
+        let bounds = |i: usize, names: &str| {
+            let (min, max) = (
+                parameter_values[i].unwrap_usize(),
+                parameter_values[i + 1].unwrap_usize(),
+            );
+            if min > max {
+                Err(anyhow!(
+                    "minimal value must not be higher than maximal one ({names})"
+                ))
+                .context(context)
+            } else {
+                Ok(min..=max)
+            }
+        };
+        let n_extensions = bounds(1, r#""emin" and "emax""#)?;
+        let extension_sizes = bounds(3, r#""smin" and "smax""#)?;
+        let grounded_sizes = bounds(5, r#""gmin" and "gmax""#)?;
+        if extension_sizes.end() + grounded_sizes.end() > n {
+            return Err(anyhow!(
+                r#"the sum of the maximal sizes ("smax" and "gmax") must not exceed the number of arguments ("n")"#
+            ))
+            .context(context);
+        }
+        Ok(Box::new(move |r| {
+            let n_grounded = r.gen_range(grounded_sizes.clone());
+            let mut arguments = (0..n).collect::<Vec<usize>>();
+            arguments.shuffle(r);
+            let (grounded, others) = arguments.split_at(n_grounded);
+            let extensions = (0..r.gen_range(n_extensions.clone()))
+                .map(|_| {
+                    let size = r.gen_range(extension_sizes.clone());
+                    index::sample(r, others.len(), size)
+                        .into_iter()
+                        .map(|i| others[i])
+                        .collect::<Vec<usize>>()
+                })
+                .collect::<Vec<Vec<usize>>>();
+            build_stable_graph(n, grounded, &extensions, r)
+        }))
+    }
+}
+
+impl<R> GeneratorFactory<Directed, R> for IccmaStableGeneratorFactory where R: Rng {}
+
+/// Builds a framework in which the given extensions, completed by the grounded arguments, are stable.
+fn build_stable_graph<R>(
+    n: usize,
+    grounded: &[usize],
+    extensions: &[Vec<usize>],
+    r: &mut R,
+) -> Graph<Directed>
+where
+    R: Rng,
+{
+    let mut membership = vec![vec![false; extensions.len()]; n];
+    for (i, extension) in extensions.iter().enumerate() {
+        extension.iter().for_each(|a| membership[*a][i] = true);
+    }
+    let can_attack = |a: usize, b: usize| {
+        membership[a]
+            .iter()
+            .zip(membership[b].iter())
+            .all(|(in_a, in_b)| !(in_a & in_b))
+    };
+    let mut is_grounded = vec![false; n];
+    grounded.iter().for_each(|a| is_grounded[*a] = true);
+    let mut g = Graph::with_capacity(n, 0);
+    (0..n).for_each(|_| g.new_node());
+    let mut attacks = HashSet::new();
+    for (i, extension) in extensions.iter().enumerate() {
+        for b in (0..n).filter(|b| !is_grounded[*b] && !membership[*b][i]) {
+            let candidates = extension
+                .iter()
+                .copied()
+                .filter(|a| can_attack(*a, b))
+                .collect::<Vec<usize>>();
+            let attacker = match candidates.choose(r) {
+                Some(a) => *a,
+                None if membership[b].iter().all(|in_ext| !in_ext) => match grounded.choose(r) {
+                    Some(a) => *a,
+                    None => continue,
+                },
+                None => continue,
+            };
+            if attacks.insert((attacker, b)) {
+                g.new_edge(attacker, b);
+            }
+        }
+    }
+    g
+}
+
+/// A factory used to build generators for argumentation frameworks following the SccGenerator family of the [ICCMA 2015](https://argumentationcompetition.org/2015/) benchmarks.
+///
+/// The arguments are split into `k` strongly connected components, made of consecutive arguments.
+/// Each component contains a random cycle through all its arguments, which makes it strongly connected,
+/// and each other possible attack between two distinct arguments of the same component is added with probability `p`.
+/// Each possible attack from an argument of a component to an argument of a later component is added with probability `q`;
+/// since no attack goes from a component to an earlier one, the components are exactly the strongly connected components of the framework.
+///
+/// The component of each argument is available through [`Graph::communities`].
+///
+/// This generator is only available for directed graphs.
+///
+/// Such factories can be created by passing `scc/k,s,p,q` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `k` is the number of components;
+///   - `s` is the size of each component;
+///   - `p` is the probability of each additional attack inside the components;
+///   - `q` is the probability of each attack between components.
+///
+/// Parameter `s` must be higher than zero.
+/// The optional parameter `smax` (eg. `scc/k,s,p,q,smax=10`) makes the size of each component be drawn uniformly between `s` and `smax`;
+/// its default value, 0, means all the components have size `s`.
+/// When it is set, `smax` must not be lower than `s`.
+#[derive(Default)]
+pub struct IccmaSccGeneratorFactory;
+
+impl<R> NamedParam<BoxedGenerator<Directed, R>> for IccmaSccGeneratorFactory
+where
+    R: Rng,
+{
+    fn name(&self) -> &'static str {
+        "scc"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator of argumentation frameworks with a given strongly connected component structure, following the ICCMA 2015 SccGenerator.",
+            "First parameter gives the number of components and the second one gives their size,",
+            "while the third and fourth ones give the probabilities of the attacks inside and between the components.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+            ParameterType::Probability,
+            ParameterType::Probability,
+        ]
+    }
+
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        vec![(
+            "smax",
+            ParameterType::PositiveInteger,
+            ParameterValue::PositiveInteger(0),
+        )]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Directed, R>> {
+        let context = "while building an ICCMA SCC generator";
+        let k = parameter_values[0].unwrap_usize();
+        let s = parameter_values[1].unwrap_usize();
+        let p = parameter_values[2].unwrap_f64();
+        let q = parameter_values[3].unwrap_f64();
+        if s == 0 {
+            return Err(anyhow!(
+                r#"second parameter ("s") must be higher than zero"#
+            ))
+            .context(context);
+        }
+        let s_max = match parameter_values[4].unwrap_usize() {
+            0 => s,
+            s_max if s_max < s => {
+                return Err(anyhow!(
+                    r#"optional parameter "smax" must not be lower than the second one ("s")"#
+                ))
+                .context(context)
+            }
+            s_max => s_max,
+        };
+        Ok(Box::new(move |r| {
+            let sizes = (0..k)
+                .map(|_| r.gen_range(s..=s_max))
+                .collect::<Vec<usize>>();
+            build_scc_graph(&sizes, p, q, r)
+        }))
+    }
+}
+
+impl<R> GeneratorFactory<Directed, R> for IccmaSccGeneratorFactory where R: Rng {}
+
+fn build_scc_graph<R>(sizes: &[usize], p: f64, q: f64, r: &mut R) -> Graph<Directed>
+where
+    R: Rng,
+{
+    let n = sizes.iter().sum();
+    let mut g = Graph::with_capacity(n, 0);
+    (0..n).for_each(|_| g.new_node());
+    let components = sizes
+        .iter()
+        .enumerate()
+        .flat_map(|(i, size)| std::iter::repeat_n(i, *size))
+        .collect::<Vec<usize>>();
+    let proba_uniform = Uniform::new_inclusive(0., 1.);
+    let mut successors = vec![usize::MAX; n];
+    let mut first = 0;
+    for size in sizes.iter().copied() {
+        let mut cycle = (first..first + size).collect::<Vec<usize>>();
+        first += size;
+        if size < 2 {
+            continue;
+        }
+        cycle.shuffle(r);
+        for (i, a) in cycle.iter().enumerate() {
+            successors[*a] = cycle[(i + 1) % size];
+            g.new_edge(*a, successors[*a]);
+        }
+    }
+    for a in 0..n {
+        for b in 0..n {
+            let proba = match components[a].cmp(&components[b]) {
+                std::cmp::Ordering::Less => q,
+                std::cmp::Ordering::Equal if a != b && successors[a] != b => p,
+                _ => continue,
+            };
+            if proba_uniform.sample(r) < proba {
+                g.new_edge(a, b);
+            }
+        }
+    }
+    g.set_communities(components);
+    g
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::ThreadRng;
+
+    fn stable_params(values: [usize; 7]) -> Vec<ParameterValue> {
+        values
+            .into_iter()
+            .map(ParameterValue::PositiveInteger)
+            .collect()
+    }
+
+    fn is_stable(g: &Graph<Directed>, extension: &[usize]) -> bool {
+        let mut attacked = vec![false; g.n_nodes()];
+        let mut inside = vec![false; g.n_nodes()];
+        extension.iter().for_each(|a| inside[*a] = true);
+        for (a, b) in g.iter_edges() {
+            if inside[a] {
+                if inside[b] {
+                    return false;
+                }
+                attacked[b] = true;
+            }
+        }
+        (0..g.n_nodes()).all(|a| inside[a] || attacked[a])
+    }
+
+    #[test]
+    fn test_grounded_is_acyclic() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = IccmaGroundedGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(50),
+                ParameterValue::Probability(0.2),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(50, g.n_nodes());
+        assert!(g.iter_edges().all(|(a, b)| a < b));
+    }
+
+    #[test]
+    fn test_stable_wrong_params() {
+        for values in [[10, 2, 1, 1, 2, 1, 2], [10, 1, 2, 3, 6, 1, 5]] {
+            assert!(
+                (IccmaStableGeneratorFactory.try_with_params(stable_params(values))
+                    as Result<BoxedGenerator<Directed, ThreadRng>>)
+                    .is_err()
+            )
+        }
+    }
+
+    #[test]
+    fn test_disjoint_extensions_are_stable() {
+        let mut rng = rand::thread_rng();
+        let grounded = [0, 1];
+        let extensions = vec![vec![2, 3], vec![4, 5, 6], vec![7]];
+        let g = build_stable_graph(10, &grounded, &extensions, &mut rng);
+        assert!(g.iter_edges().all(|(_, b)| b > 1));
+        for extension in extensions.iter() {
+            let mut full = extension.clone();
+            full.extend_from_slice(&grounded);
+            assert!(is_stable(&g, &full));
+        }
+    }
+
+    #[test]
+    fn test_stable() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = IccmaStableGeneratorFactory
+            .try_with_params(stable_params([100, 2, 5, 5, 20, 0, 10]))
+            .unwrap()(&mut rng);
+        assert_eq!(100, g.n_nodes());
+        assert!(g.iter_edges().all(|(a, b)| a != b));
+        assert!(g.n_edges() > 0);
+    }
+
+    fn scc_params(k: usize, s: usize, p: f64, q: f64, s_max: usize) -> Vec<ParameterValue> {
+        vec![
+            ParameterValue::PositiveInteger(k),
+            ParameterValue::PositiveInteger(s),
+            ParameterValue::Probability(p),
+            ParameterValue::Probability(q),
+            ParameterValue::PositiveInteger(s_max),
+        ]
+    }
+
+    #[test]
+    fn test_scc_smax_lower_than_s() {
+        assert!(
+            (IccmaSccGeneratorFactory.try_with_params(scc_params(3, 5, 0.5, 0.5, 4))
+                as Result<BoxedGenerator<Directed, ThreadRng>>)
+                .is_err()
+        )
+    }
+
+    #[test]
+    fn test_scc_empty_components() {
+        assert!(
+            (IccmaSccGeneratorFactory.try_with_params(scc_params(3, 0, 0.5, 0.5, 0))
+                as Result<BoxedGenerator<Directed, ThreadRng>>)
+                .is_err()
+        );
+        assert!(
+            (IccmaSccGeneratorFactory.try_with_params(scc_params(3, 0, 0.5, 0.5, 2))
+                as Result<BoxedGenerator<Directed, ThreadRng>>)
+                .is_err()
+        )
+    }
+
+    #[test]
+    fn test_scc_components() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = IccmaSccGeneratorFactory
+            .try_with_params(scc_params(4, 5, 0., 0.3, 0))
+            .unwrap()(&mut rng);
+        assert_eq!(20, g.n_nodes());
+        let components = g.communities().unwrap();
+        assert_eq!(
+            (0..4).flat_map(|i| [i; 5]).collect::<Vec<usize>>(),
+            components
+        );
+        let edges = g.iter_edges().collect::<Vec<(usize, usize)>>();
+        assert!(edges.iter().all(|(a, b)| components[*a] < components[*b]
+            || (components[*a] == components[*b] && a != b)));
+        assert_eq!(
+            20,
+            edges
+                .iter()
+                .filter(|(a, b)| components[*a] == components[*b])
+                .count()
+        );
+        let petgraph = petgraph::Graph::<(), ()>::from_edges(
+            edges.iter().map(|(a, b)| (*a as u32, *b as u32)),
+        );
+        assert_eq!(4, petgraph::algo::kosaraju_scc(&petgraph).len());
+    }
+
+    #[test]
+    fn test_scc_sizes() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = IccmaSccGeneratorFactory
+            .try_with_params(scc_params(10, 1, 1., 0., 3))
+            .unwrap()(&mut rng);
+        let components = g.communities().unwrap();
+        let mut sizes = [0; 10];
+        components.iter().for_each(|c| sizes[*c] += 1);
+        assert!(sizes.iter().all(|s| (1..=3).contains(s)));
+        let expected_edges = sizes.iter().map(|s| s * (s - 1)).sum::<usize>();
+        assert_eq!(expected_edges, g.n_edges());
+    }
+}
@@ -0,0 +1,217 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::Result;
+use petgraph::EdgeType;
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    Rng,
+};
+use std::collections::HashSet;
+
+/// A factory used to build generators for [Kleinberg's navigable small-world](https://doi.org/10.1145/335305.335325) graphs.
+///
+/// Nodes are laid out on a square grid; the node at column `x` and row `y` has label `y*n+x`.
+/// Each node is linked to its grid neighbors, and gets a given number of long-range links.
+/// The target of each long-range link is drawn with a probability proportional to `d^-r`,
+/// where `d` is its Manhattan distance to the source.
+/// Long-range links that duplicate existing edges are discarded.
+///
+/// The position of each node on the grid is available through [`Graph::positions`].
+///
+/// In directed graphs generated by this objects, grid edges are set in both directions, and long-range links go from the node they were drawn for.
+///
+/// Such factories can be created by passing `kleinberg/n,q,r` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the number of rows (and columns) of the grid;
+///   - `q` is the number of long-range links drawn for each node;
+///   - `r` is the exponent of the long-range link distribution (the graphs are navigable when it is equal to 2).
+#[derive(Default)]
+pub struct KleinbergGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for KleinbergGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "kleinberg"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following Kleinberg's navigable small-world model on a square grid.",
+            "First parameter gives the side of the grid, the second one gives the number of long-range links per node, and the third one gives the exponent of their distance distribution.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveFloat,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let side = parameter_values[0].unwrap_usize();
+        let q = parameter_values[1].unwrap_usize();
+        let exponent = parameter_values[2].unwrap_f64();
+        Ok(Box::new(move |r| build_graph(side, q, exponent, r)))
+    }
+}
+
+fn build_graph<Ty, R>(side: usize, q: usize, exponent: f64, r: &mut R) -> Graph<Ty>
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    let n = side * side;
+    let mut g = Graph::with_capacity(n, 0);
+    (0..n).for_each(|_| g.new_node());
+    let mut edges = HashSet::new();
+    let mut add_edge = |g: &mut Graph<Ty>, from: usize, to: usize| {
+        let key = if Ty::is_directed() {
+            (from, to)
+        } else {
+            (usize::min(from, to), usize::max(from, to))
+        };
+        if edges.insert(key) {
+            g.new_edge(from, to);
+        }
+    };
+    for i in 0..n {
+        let (x, y) = (i % side, i / side);
+        if x + 1 < side {
+            add_edge(&mut g, i, i + 1);
+            add_edge(&mut g, i + 1, i);
+        }
+        if y + 1 < side {
+            add_edge(&mut g, i, i + side);
+            add_edge(&mut g, i + side, i);
+        }
+    }
+    if n > 1 {
+        // distances are drawn first, weighted by the number of nodes at this distance in an infinite grid (4d)
+        let max_distance = 2 * (side - 1);
+        let distances = WeightedIndex::new(
+            (1..=max_distance).map(|d| 4. * d as f64 * (d as f64).powf(-exponent)),
+        )
+        .unwrap();
+        for i in 0..n {
+            let (x, y) = (i % side, i / side);
+            for _ in 0..q {
+                let target = loop {
+                    let d = 1 + distances.sample(r);
+                    let (dx, dy) = diamond_offset(d, r.gen_range(0..4 * d));
+                    let (tx, ty) = (x as isize + dx, y as isize + dy);
+                    if (0..side as isize).contains(&tx) && (0..side as isize).contains(&ty) {
+                        break ty as usize * side + tx as usize;
+                    }
+                };
+                add_edge(&mut g, i, target);
+            }
+        }
+    }
+    g.set_positions(
+        (0..n)
+            .map(|i| vec![(i % side) as f64, (i / side) as f64])
+            .collect(),
+    );
+    g
+}
+
+/// Returns the offset of the `k`-th point of the grid at Manhattan distance `d` from the origin, for `k` lower than `4d`.
+fn diamond_offset(d: usize, k: usize) -> (isize, isize) {
+    let (d, t) = (d as isize, (k % d) as isize);
+    match k / d as usize {
+        0 => (d - t, t),
+        1 => (-t, d - t),
+        2 => (t - d, -t),
+        _ => (t, t - d),
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for KleinbergGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+    use petgraph::{Directed, Undirected};
+
+    #[test]
+    fn test_no_long_range_links() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = KleinbergGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::PositiveInteger(0),
+                ParameterValue::PositiveFloat(2.),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(4, g.n_nodes());
+        assert_eq!(
+            vec![(0, 1), (0, 2), (1, 3), (2, 3)],
+            g.iter_edges()
+                .collect::<Vec<(NodeIndexType, NodeIndexType)>>()
+        );
+        assert_eq!(
+            Some([vec![0., 0.], vec![1., 0.], vec![0., 1.], vec![1., 1.]].as_slice()),
+            g.positions()
+        );
+    }
+
+    #[test]
+    fn test_long_range_links() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = KleinbergGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(10),
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::PositiveFloat(2.),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(100, g.n_nodes());
+        let edges = g.iter_edges().collect::<HashSet<(usize, usize)>>();
+        assert_eq!(g.n_edges(), edges.len());
+        assert!(edges.iter().all(|(a, b)| a != b));
+        let mut out_degrees = vec![0; 100];
+        edges.iter().for_each(|(a, _)| out_degrees[*a] += 1);
+        assert!(out_degrees.iter().all(|d| *d <= 6));
+        assert!(g.n_edges() > 2 * 2 * 10 * 9);
+    }
+
+    #[test]
+    fn test_single_node() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = KleinbergGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(1),
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::PositiveFloat(2.),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(1, g.n_nodes());
+        assert_eq!(0, g.n_edges());
+    }
+
+    #[test]
+    fn test_diamond_offsets() {
+        for d in 1..5 {
+            let offsets = (0..4 * d)
+                .map(|k| diamond_offset(d, k))
+                .collect::<HashSet<(isize, isize)>>();
+            assert_eq!(4 * d, offsets.len());
+            assert!(offsets
+                .iter()
+                .all(|(dx, dy)| (dx.unsigned_abs() + dy.unsigned_abs()) == d));
+        }
+    }
+}
@@ -0,0 +1,593 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::EdgeType;
+use rand::Rng;
+use std::collections::HashSet;
+
+/// A factory used to build generators for two-dimensional grids.
+///
+/// Nodes are laid out in rows; the node at column `x` and row `y` has label `y*w+x`.
+/// Each node is linked to its right and bottom neighbors.
+/// When the optional parameter `periodic` is set to `true`, the grid is wrapped into a torus:
+/// the nodes of the last column (resp. row) are linked to the ones of the first column (resp. row).
+///
+/// In directed graphs generated by this objects, edges go from nodes to their right and bottom neighbors.
+/// If the optional parameter `both` is set to `true`, each edge is replaced by two edges in opposite directions.
+///
+/// Such factories can be created by passing `grid/w,h` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `w` is the number of columns;
+///   - `h` is the number of rows.
+///
+/// The optional parameters `periodic` and `both` (eg. `grid/w,h,periodic=true,both=true`) default to `false`; `both` can only be set for directed graphs.
+/// For periodic grids, the self-loops and multiple edges that would be produced by sides lower than 3 are discarded.
+#[derive(Default)]
+pub struct GridGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for GridGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "grid"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing two-dimensional grids.",
+            "First parameter gives the number of columns, while the second one gives the number of rows.",
+            "The grid is wrapped into a torus if the optional parameter \"periodic\" is set to true.",
+            "In directed graphs, edges are set in both directions if the optional parameter \"both\" is set to true.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+        ]
+    }
+
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        lattice_optional_parameters()
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a grid generator";
+        let sides = [
+            parameter_values[0].unwrap_usize(),
+            parameter_values[1].unwrap_usize(),
+        ];
+        let periodic = parameter_values[2].unwrap_bool();
+        let both = parameter_values[3].unwrap_bool();
+        let n = n_nodes(&sides).context(context)?;
+        lattice_generator(n, hypergrid_edges(n, &sides, periodic), both).context(context)
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for GridGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// A factory used to build generators for k-dimensional grids.
+///
+/// The grid has the same number of nodes `s` on each dimension, giving `s^k` nodes.
+/// The node which coordinates are `(c_0, ..., c_{k-1})` has label `c_0 + c_1*s + ... + c_{k-1}*s^{k-1}`.
+/// Each node is linked to its successor on each dimension.
+/// When the optional parameter `periodic` is set to `true`, the grid is wrapped into a torus.
+///
+/// In directed graphs generated by this objects, edges go from nodes to their successors.
+/// If the optional parameter `both` is set to `true`, each edge is replaced by two edges in opposite directions.
+///
+/// Such factories can be created by passing `kgrid/k,s` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `k` is the number of dimensions;
+///   - `s` is the number of nodes on each dimension.
+///
+/// The optional parameters `periodic` and `both` (eg. `kgrid/k,s,periodic=true,both=true`) default to `false`; `both` can only be set for directed graphs.
+/// For periodic grids, the self-loops and multiple edges that would be produced by sides lower than 3 are discarded.
+#[derive(Default)]
+pub struct HypergridGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for HypergridGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "kgrid"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing k-dimensional grids.",
+            "First parameter gives the number of dimensions, while the second one gives the number of nodes on each dimension.",
+            "The grid is wrapped into a torus if the optional parameter \"periodic\" is set to true.",
+            "In directed graphs, edges are set in both directions if the optional parameter \"both\" is set to true.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+        ]
+    }
+
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        lattice_optional_parameters()
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a k-dimensional grid generator";
+        let k = parameter_values[0].unwrap_usize();
+        let s = parameter_values[1].unwrap_usize();
+        let periodic = parameter_values[2].unwrap_bool();
+        let both = parameter_values[3].unwrap_bool();
+        let n = u32::try_from(k)
+            .ok()
+            .and_then(|k| s.checked_pow(k))
+            .ok_or_else(|| anyhow!("too many nodes"))
+            .context(context)?;
+        lattice_generator(n, hypergrid_edges(n, &vec![s; k], periodic), both).context(context)
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for HypergridGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// A factory used to build generators for triangular lattices.
+///
+/// The lattice is built from a two-dimensional grid (see [`GridGeneratorFactory`]) by adding an edge between each node and its bottom-right neighbor.
+/// When the optional parameter `periodic` is set to `true`, the lattice is wrapped into a torus.
+///
+/// In directed graphs generated by this objects, edges go from nodes to their right, bottom and bottom-right neighbors.
+/// If the optional parameter `both` is set to `true`, each edge is replaced by two edges in opposite directions.
+///
+/// Such factories can be created by passing `tri/w,h` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `w` is the number of columns;
+///   - `h` is the number of rows.
+///
+/// The optional parameters `periodic` and `both` (eg. `tri/w,h,periodic=true,both=true`) default to `false`; `both` can only be set for directed graphs.
+/// For periodic lattices, the self-loops and multiple edges that would be produced by sides lower than 3 are discarded.
+#[derive(Default)]
+pub struct TriangularLatticeGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for TriangularLatticeGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "tri"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing triangular lattices.",
+            "First parameter gives the number of columns, while the second one gives the number of rows.",
+            "The lattice is wrapped into a torus if the optional parameter \"periodic\" is set to true.",
+            "In directed graphs, edges are set in both directions if the optional parameter \"both\" is set to true.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+        ]
+    }
+
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        lattice_optional_parameters()
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a triangular lattice generator";
+        let w = parameter_values[0].unwrap_usize();
+        let h = parameter_values[1].unwrap_usize();
+        let periodic = parameter_values[2].unwrap_bool();
+        let both = parameter_values[3].unwrap_bool();
+        let n = n_nodes(&[w, h]).context(context)?;
+        let mut edges = hypergrid_edges(n, &[w, h], periodic);
+        for y in 0..h {
+            for x in 0..w {
+                if (x + 1 < w && y + 1 < h) || periodic {
+                    edges.push((y * w + x, ((y + 1) % h) * w + (x + 1) % w));
+                }
+            }
+        }
+        lattice_generator(n, edges, both).context(context)
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for TriangularLatticeGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// A factory used to build generators for hexagonal lattices (honeycombs).
+///
+/// The lattice is given in its "brick wall" representation: it is built from a two-dimensional grid (see [`GridGeneratorFactory`])
+/// by keeping all the horizontal edges, but only the vertical edges which top node `(x, y)` is such that `x+y` is even.
+/// When the optional parameter `periodic` is set to `true`, the lattice is wrapped into a torus; in this case, the numbers of columns and rows must be even.
+///
+/// In directed graphs generated by this objects, edges go from nodes to their right and bottom neighbors.
+/// If the optional parameter `both` is set to `true`, each edge is replaced by two edges in opposite directions.
+///
+/// Such factories can be created by passing `hex/w,h` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `w` is the number of columns;
+///   - `h` is the number of rows.
+///
+/// The optional parameters `periodic` and `both` (eg. `hex/w,h,periodic=true,both=true`) default to `false`; `both` can only be set for directed graphs.
+/// For periodic lattices, the self-loops and multiple edges that would be produced by sides lower than 3 are discarded.
+#[derive(Default)]
+pub struct HexagonalLatticeGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for HexagonalLatticeGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "hex"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing hexagonal lattices.",
+            "First parameter gives the number of columns, while the second one gives the number of rows.",
+            "The lattice is wrapped into a torus if the optional parameter \"periodic\" is set to true (the numbers of columns and rows must be even).",
+            "In directed graphs, edges are set in both directions if the optional parameter \"both\" is set to true.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+        ]
+    }
+
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        lattice_optional_parameters()
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a hexagonal lattice generator";
+        let w = parameter_values[0].unwrap_usize();
+        let h = parameter_values[1].unwrap_usize();
+        let periodic = parameter_values[2].unwrap_bool();
+        let both = parameter_values[3].unwrap_bool();
+        if periodic && (w & 1 == 1 || h & 1 == 1) {
+            return Err(anyhow!(
+                r#"parameters ("w" and "h") must be even for periodic lattices"#
+            ))
+            .context(context);
+        }
+        let n = n_nodes(&[w, h]).context(context)?;
+        let edges = hypergrid_edges(n, &[w, h], periodic)
+            .into_iter()
+            .filter(|(from, to)| {
+                let (x, y) = (from % w, from / w);
+                y == to / w || (x + y) & 1 == 0
+            })
+            .collect();
+        lattice_generator(n, edges, both).context(context)
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for HexagonalLatticeGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+fn lattice_optional_parameters() -> Vec<(&'static str, ParameterType, ParameterValue)> {
+    vec![
+        (
+            "periodic",
+            ParameterType::Boolean,
+            ParameterValue::Boolean(false),
+        ),
+        (
+            "both",
+            ParameterType::Boolean,
+            ParameterValue::Boolean(false),
+        ),
+    ]
+}
+
+/// Computes the number of nodes of a grid given the number of nodes on each dimension.
+fn n_nodes(sides: &[usize]) -> Result<usize> {
+    sides
+        .iter()
+        .try_fold(1_usize, |n, side| n.checked_mul(*side))
+        .ok_or_else(|| anyhow!("too many nodes"))
+}
+
+/// Computes the forward edges of a grid given its number of nodes `n` and the number of nodes on each dimension.
+///
+/// Nodes are labeled such that the first dimension is the one with the smallest stride.
+fn hypergrid_edges(n: usize, sides: &[usize], periodic: bool) -> Vec<(usize, usize)> {
+    let mut edges = Vec::with_capacity(n * sides.len());
+    for node in 0..n {
+        let mut stride = 1;
+        for side in sides {
+            let coordinate = (node / stride) % side;
+            if coordinate + 1 < *side {
+                edges.push((node, node + stride));
+            } else if periodic {
+                edges.push((node, node - coordinate * stride));
+            }
+            stride *= side;
+        }
+    }
+    edges
+}
+
+/// Builds a generator producing the lattice given by its number of nodes and its forward edges.
+///
+/// Self-loops and multiple edges are discarded.
+/// If `both` is set, each edge is replaced by two edges in opposite directions.
+fn lattice_generator<Ty, R>(
+    n: usize,
+    edges: Vec<(usize, usize)>,
+    both: bool,
+) -> Result<BoxedGenerator<Ty, R>>
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    if both && !Ty::is_directed() {
+        return Err(anyhow!(
+            r#"optional parameter "both" is only available for directed graphs"#
+        ));
+    }
+    let mut edge_set = HashSet::with_capacity(edges.len());
+    let edges = edges
+        .into_iter()
+        .filter(|(from, to)| from != to && edge_set.insert((*from.min(to), *from.max(to))))
+        .collect::<Vec<(usize, usize)>>();
+    Ok(Box::new(move |_| {
+        let mut g = Graph::with_capacity(n, if both { 2 } else { 1 } * edges.len());
+        (0..n).for_each(|_| g.new_node());
+        edges.iter().for_each(|(from, to)| {
+            g.new_edge(*from, *to);
+            if both {
+                g.new_edge(*to, *from);
+            }
+        });
+        g
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+    use petgraph::{Directed, Undirected};
+    use rand::rngs::ThreadRng;
+
+    fn sorted_edges<Ty>(g: &Graph<Ty>) -> Vec<(NodeIndexType, NodeIndexType)>
+    where
+        Ty: EdgeType,
+    {
+        let mut edges = g
+            .iter_edges()
+            .collect::<Vec<(NodeIndexType, NodeIndexType)>>();
+        edges.sort_unstable();
+        edges
+    }
+
+    #[test]
+    fn test_grid() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = GridGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::Boolean(false),
+                ParameterValue::Boolean(false),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(6, g.n_nodes());
+        assert_eq!(
+            vec![(0, 1), (0, 3), (1, 2), (1, 4), (2, 5), (3, 4), (4, 5)],
+            sorted_edges(&g)
+        );
+    }
+
+    #[test]
+    fn test_grid_both() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = GridGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::PositiveInteger(1),
+                ParameterValue::Boolean(false),
+                ParameterValue::Boolean(true),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(vec![(0, 1), (1, 0)], sorted_edges(&g));
+    }
+
+    #[test]
+    fn test_both_undirected() {
+        assert!((GridGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(2),
+            ParameterValue::PositiveInteger(1),
+            ParameterValue::Boolean(false),
+            ParameterValue::Boolean(true),
+        ]) as Result<BoxedGenerator<Undirected, ThreadRng>>)
+            .is_err());
+    }
+
+    #[test]
+    fn test_torus() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = GridGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::Boolean(true),
+                ParameterValue::Boolean(false),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(9, g.n_nodes());
+        assert_eq!(18, g.n_edges());
+    }
+
+    #[test]
+    fn test_small_torus() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = GridGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::PositiveInteger(1),
+                ParameterValue::Boolean(true),
+                ParameterValue::Boolean(false),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(vec![(0, 1)], sorted_edges(&g));
+    }
+
+    #[test]
+    fn test_hypergrid() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = HypergridGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::Boolean(false),
+                ParameterValue::Boolean(false),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(8, g.n_nodes());
+        assert_eq!(12, g.n_edges());
+    }
+
+    #[test]
+    fn test_hypergrid_too_large() {
+        assert!((HypergridGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(100),
+            ParameterValue::PositiveInteger(10),
+            ParameterValue::Boolean(false),
+            ParameterValue::Boolean(false),
+        ]) as Result<BoxedGenerator<Undirected, ThreadRng>>)
+            .is_err())
+    }
+
+    #[test]
+    fn test_grid_too_large() {
+        assert!((GridGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(usize::MAX),
+            ParameterValue::PositiveInteger(2),
+            ParameterValue::Boolean(false),
+            ParameterValue::Boolean(false),
+        ]) as Result<BoxedGenerator<Undirected, ThreadRng>>)
+            .is_err());
+        assert!((HexagonalLatticeGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(usize::MAX),
+            ParameterValue::PositiveInteger(2),
+            ParameterValue::Boolean(false),
+            ParameterValue::Boolean(false),
+        ]) as Result<BoxedGenerator<Undirected, ThreadRng>>)
+            .is_err());
+    }
+
+    #[test]
+    fn test_triangular() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = TriangularLatticeGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::Boolean(false),
+                ParameterValue::Boolean(false),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(
+            vec![(0, 1), (0, 2), (0, 3), (1, 3), (2, 3)],
+            sorted_edges(&g)
+        );
+    }
+
+    #[test]
+    fn test_hexagonal() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = HexagonalLatticeGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::Boolean(false),
+                ParameterValue::Boolean(false),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(
+            vec![(0, 1), (0, 3), (1, 2), (2, 5), (3, 4), (4, 5)],
+            sorted_edges(&g)
+        );
+    }
+
+    #[test]
+    fn test_periodic_hexagonal() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = HexagonalLatticeGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(6),
+                ParameterValue::PositiveInteger(4),
+                ParameterValue::Boolean(true),
+                ParameterValue::Boolean(false),
+            ])
+            .unwrap()(&mut rng);
+        let mut degrees = vec![0; g.n_nodes()];
+        g.iter_edges().for_each(|(from, to)| {
+            degrees[from] += 1;
+            degrees[to] += 1;
+        });
+        assert_eq!(vec![3; 24], degrees);
+        let parity = |node: usize| (node % 6 + node / 6) & 1;
+        assert!(g.iter_edges().all(|(from, to)| parity(from) != parity(to)));
+    }
+
+    #[test]
+    fn test_periodic_hexagonal_odd_sides() {
+        assert!((HexagonalLatticeGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(4),
+            ParameterValue::PositiveInteger(3),
+            ParameterValue::Boolean(true),
+            ParameterValue::Boolean(false),
+        ]) as Result<BoxedGenerator<Undirected, ThreadRng>>)
+            .is_err());
+        assert!((HexagonalLatticeGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(5),
+            ParameterValue::PositiveInteger(4),
+            ParameterValue::Boolean(true),
+            ParameterValue::Boolean(false),
+        ]) as Result<BoxedGenerator<Undirected, ThreadRng>>)
+            .is_err());
+    }
+}
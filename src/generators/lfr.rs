@@ -0,0 +1,383 @@
+use super::{
+    degree_sequences::{DegreeDistribution, DegreeSampler},
+    BoxedGenerator, GeneratorFactory,
+};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::EdgeType;
+use rand::{distributions::Distribution, seq::SliceRandom, Rng};
+use std::collections::HashSet;
+
+/// The number of times stubs that could not be matched are shuffled and matched again.
+const N_MATCHING_ROUNDS: usize = 10;
+
+/// A factory used to build generators for [Lancichinetti-Fortunato-Radicchi](https://en.wikipedia.org/wiki/Lancichinetti%E2%80%93Fortunato%E2%80%93Radicchi_benchmark) benchmark graphs.
+///
+/// Node degrees and community sizes follow truncated power-law distributions.
+/// Each node shares a fraction `1-mu` of its edges with the nodes of its community, and a fraction `mu` with the other nodes.
+/// Edges are built by matching stubs, discarding self-loops and multiple edges;
+/// thus, the degrees and the mixing parameter of the resulting graphs are close to the expected ones, but may not be exactly equal.
+///
+/// The community of each node is available through [`Graph::communities`].
+///
+/// In directed graphs generated by this objects, each edge is given a random direction.
+///
+/// Such factories can be created by passing `lfr/n,kmin,kmax,mu,t1,t2` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the number of nodes;
+///   - `kmin` and `kmax` are the minimal and maximal degrees;
+///   - `mu` is the mixing parameter, that is the fraction of the edges of each node going out of its community;
+///   - `t1` is the exponent of the degree distribution;
+///   - `t2` is the exponent of the community size distribution.
+///
+/// The community sizes are bounded by the optional parameters `cmin` and `cmax` (e.g. `lfr/1000,10,50,0.2,2,1.5,cmin=20,cmax=100`).
+/// When they are not set (or set to 0), they are equal to `kmin` and `kmax`.
+/// Community sizes are adjusted to sum to `n`, and nodes whose internal degree does not fit in any community see it reduced.
+/// Both may lead to communities that do not respect the size bounds or nodes that do not respect the degree bounds.
+#[derive(Default)]
+pub struct LfrGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for LfrGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "lfr"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the Lancichinetti-Fortunato-Radicchi benchmark model.",
+            "First parameter gives the number of nodes, the second and third ones give the minimal and maximal degrees, and the fourth one gives the mixing parameter.",
+            "The last two parameters give the exponents of the degree and community size distributions.",
+            "Community sizes are bounded by the optional parameters \"cmin\" and \"cmax\" (defaulting to the degree bounds).",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+            ParameterType::Probability,
+            ParameterType::PositiveFloat,
+            ParameterType::PositiveFloat,
+        ]
+    }
+
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        vec![
+            (
+                "cmin",
+                ParameterType::PositiveInteger,
+                ParameterValue::PositiveInteger(0),
+            ),
+            (
+                "cmax",
+                ParameterType::PositiveInteger,
+                ParameterValue::PositiveInteger(0),
+            ),
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a LFR generator";
+        let n = parameter_values[0].unwrap_usize();
+        let min_degree = parameter_values[1].unwrap_usize();
+        let max_degree = parameter_values[2].unwrap_usize();
+        let mu = parameter_values[3].unwrap_f64();
+        let degree_exponent = parameter_values[4].unwrap_f64();
+        let size_exponent = parameter_values[5].unwrap_f64();
+        let min_size = match parameter_values[6].unwrap_usize() {
+            0 => min_degree,
+            s => s,
+        };
+        let max_size = match parameter_values[7].unwrap_usize() {
+            0 => max_degree,
+            s => s,
+        };
+        if min_degree == 0 || min_degree > max_degree || max_degree >= n {
+            return Err(anyhow!(
+                r#"degree bounds ("kmin" and "kmax") must verify 0 < kmin <= kmax < n"#
+            ))
+            .context(context);
+        }
+        if min_size > max_size || max_size > n {
+            return Err(anyhow!(
+                r#"community size bounds ("cmin" and "cmax") must verify 0 < cmin <= cmax <= n"#
+            ))
+            .context(context);
+        }
+        let degree_sampler =
+            DegreeDistribution::BoundedPowerLaw(degree_exponent, min_degree, max_degree)
+                .sampler(n)
+                .context(r#"while building the degree distribution (with exponent "t1")"#)
+                .context(context)?;
+        let size_sampler = DegreeDistribution::BoundedPowerLaw(size_exponent, min_size, max_size)
+            .sampler(n)
+            .context(r#"while building the community size distribution (with exponent "t2")"#)
+            .context(context)?;
+        Ok(Box::new(move |r| {
+            let sizes = sample_community_sizes(n, &size_sampler, (min_size, max_size), r);
+            build_graph(n, &degree_sampler, sizes, mu, r)
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for LfrGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+fn build_graph<Ty, R>(
+    n: usize,
+    degree_sampler: &DegreeSampler,
+    sizes: Vec<usize>,
+    mu: f64,
+    r: &mut R,
+) -> Graph<Ty>
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    let degrees = degree_sampler.sample_sequence(n, false, r);
+    let mut internal_degrees = degrees
+        .iter()
+        .map(|d| ((1. - mu) * *d as f64).round() as usize)
+        .collect::<Vec<usize>>();
+    let external_degrees = degrees
+        .iter()
+        .zip(internal_degrees.iter())
+        .map(|(d, i)| d - i)
+        .collect::<Vec<usize>>();
+    let communities = assign_communities(&sizes, &mut internal_degrees, r);
+    let mut members = vec![vec![]; sizes.len()];
+    communities
+        .iter()
+        .enumerate()
+        .for_each(|(i, c)| members[*c].push(i));
+    let mut edges = HashSet::new();
+    let mut ordered_edges = Vec::new();
+    for community_members in members.iter() {
+        let stubs = community_members
+            .iter()
+            .flat_map(|i| std::iter::repeat_n(*i, internal_degrees[*i]))
+            .collect::<Vec<usize>>();
+        match_stubs(stubs, &mut edges, &mut ordered_edges, |_, _| true, r);
+    }
+    let stubs = (0..n)
+        .flat_map(|i| std::iter::repeat_n(i, external_degrees[i]))
+        .collect::<Vec<usize>>();
+    match_stubs(
+        stubs,
+        &mut edges,
+        &mut ordered_edges,
+        |a, b| communities[a] != communities[b],
+        r,
+    );
+    let mut g = Graph::with_capacity(n, ordered_edges.len());
+    (0..n).for_each(|_| g.new_node());
+    for (a, b) in ordered_edges {
+        if Ty::is_directed() && r.gen_bool(0.5) {
+            g.new_edge(b, a);
+        } else {
+            g.new_edge(a, b);
+        }
+    }
+    g.set_communities(communities);
+    g
+}
+
+/// Samples community sizes until they sum to `n`.
+///
+/// The last community is truncated to fit; if it becomes smaller than the minimal size,
+/// its nodes are spread among the other communities (preferably the ones that did not reach the maximal size).
+fn sample_community_sizes<R>(
+    n: usize,
+    size_sampler: &DegreeSampler,
+    (min_size, max_size): (usize, usize),
+    r: &mut R,
+) -> Vec<usize>
+where
+    R: Rng,
+{
+    let mut sizes = vec![];
+    let mut total = 0;
+    while total < n {
+        let size = usize::min(size_sampler.sample(r), n - total);
+        sizes.push(size);
+        total += size;
+    }
+    if sizes.len() > 1 && *sizes.last().unwrap() < min_size {
+        let remaining = sizes.pop().unwrap();
+        for _ in 0..remaining {
+            let not_full = (0..sizes.len())
+                .filter(|i| sizes[*i] < max_size)
+                .collect::<Vec<usize>>();
+            let i = match not_full.choose(r) {
+                Some(i) => *i,
+                None => r.gen_range(0..sizes.len()),
+            };
+            sizes[i] += 1;
+        }
+    }
+    sizes
+}
+
+/// Assigns nodes to communities given their sizes, such that the internal degree of each node is lower than the size of its community.
+///
+/// Nodes are processed by decreasing internal degrees, and are put in random communities among the ones large enough to host them.
+/// If there is no such community with some room left, the node is put in the largest one with some room left, and its internal degree is reduced.
+fn assign_communities<R>(sizes: &[usize], internal_degrees: &mut [usize], r: &mut R) -> Vec<usize>
+where
+    R: Rng,
+{
+    let mut nodes = (0..internal_degrees.len()).collect::<Vec<usize>>();
+    nodes.shuffle(r);
+    nodes.sort_by_key(|i| std::cmp::Reverse(internal_degrees[*i]));
+    let mut communities_by_size = (0..sizes.len()).collect::<Vec<usize>>();
+    communities_by_size.sort_by_key(|c| std::cmp::Reverse(sizes[*c]));
+    let mut room = sizes.to_vec();
+    let mut n_eligible = 0;
+    let mut open = vec![];
+    let mut communities = vec![0; internal_degrees.len()];
+    for node in nodes {
+        while n_eligible < sizes.len()
+            && sizes[communities_by_size[n_eligible]] > internal_degrees[node]
+        {
+            open.push(communities_by_size[n_eligible]);
+            n_eligible += 1;
+        }
+        open.retain(|c| room[*c] > 0);
+        let community = match open.choose(r) {
+            Some(c) => *c,
+            None => {
+                let c = *communities_by_size.iter().find(|c| room[**c] > 0).unwrap();
+                internal_degrees[node] = sizes[c] - 1;
+                c
+            }
+        };
+        room[community] -= 1;
+        communities[node] = community;
+    }
+    communities
+}
+
+/// Matches stubs to build edges, discarding self-loops, multiple edges and edges rejected by the filter.
+///
+/// The stubs that cannot be matched are shuffled again and again for a bounded number of rounds, and then dropped.
+fn match_stubs<F, R>(
+    mut stubs: Vec<usize>,
+    edges: &mut HashSet<(usize, usize)>,
+    ordered_edges: &mut Vec<(usize, usize)>,
+    filter: F,
+    r: &mut R,
+) where
+    F: Fn(usize, usize) -> bool,
+    R: Rng,
+{
+    for _ in 0..N_MATCHING_ROUNDS {
+        if stubs.len() < 2 {
+            break;
+        }
+        stubs.shuffle(r);
+        let mut unmatched = vec![];
+        for pair in stubs.chunks(2) {
+            if pair.len() < 2 {
+                unmatched.push(pair[0]);
+                continue;
+            }
+            let edge = (usize::min(pair[0], pair[1]), usize::max(pair[0], pair[1]));
+            if edge.0 != edge.1 && filter(edge.0, edge.1) && edges.insert(edge) {
+                ordered_edges.push(edge);
+            } else {
+                unmatched.append(&mut pair.to_vec());
+            }
+        }
+        stubs = unmatched;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use petgraph::Undirected;
+    use rand::rngs::ThreadRng;
+
+    fn lfr_params(n: usize, kmin: usize, kmax: usize, mu: f64) -> Vec<ParameterValue> {
+        vec![
+            ParameterValue::PositiveInteger(n),
+            ParameterValue::PositiveInteger(kmin),
+            ParameterValue::PositiveInteger(kmax),
+            ParameterValue::Probability(mu),
+            ParameterValue::PositiveFloat(2.),
+            ParameterValue::PositiveFloat(1.5),
+            ParameterValue::PositiveInteger(0),
+            ParameterValue::PositiveInteger(0),
+        ]
+    }
+
+    #[test]
+    fn test_wrong_degree_bounds() {
+        assert!(
+            (LfrGeneratorFactory.try_with_params(lfr_params(100, 10, 5, 0.1))
+                as Result<BoxedGenerator<Undirected, ThreadRng>>)
+                .is_err()
+        );
+        assert!(
+            (LfrGeneratorFactory.try_with_params(lfr_params(100, 10, 100, 0.1))
+                as Result<BoxedGenerator<Undirected, ThreadRng>>)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_extreme_exponent() {
+        let mut params = lfr_params(100, 5, 10, 0.1);
+        params[4] = ParameterValue::PositiveFloat(2000.);
+        assert!((LfrGeneratorFactory.try_with_params(params)
+            as Result<BoxedGenerator<Undirected, ThreadRng>>)
+            .is_err());
+    }
+
+    #[test]
+    fn test_communities() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = LfrGeneratorFactory
+            .try_with_params(lfr_params(500, 5, 30, 0.2))
+            .unwrap()(&mut rng);
+        assert_eq!(500, g.n_nodes());
+        let communities = g.communities().unwrap();
+        let mut sizes = vec![0; 1 + *communities.iter().max().unwrap()];
+        communities.iter().for_each(|c| sizes[*c] += 1);
+        assert!(sizes.iter().all(|s| *s >= 5));
+        let edges = g
+            .iter_edges()
+            .map(|(a, b)| (usize::min(a, b), usize::max(a, b)))
+            .collect::<HashSet<(usize, usize)>>();
+        assert_eq!(g.n_edges(), edges.len());
+        assert!(edges.iter().all(|(a, b)| a != b));
+        let n_external = edges
+            .iter()
+            .filter(|(a, b)| communities[*a] != communities[*b])
+            .count();
+        assert!(n_external < edges.len() / 2);
+    }
+
+    #[test]
+    fn test_no_mixing() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = LfrGeneratorFactory
+            .try_with_params(lfr_params(200, 3, 10, 0.))
+            .unwrap()(&mut rng);
+        let communities = g.communities().unwrap();
+        assert!(g
+            .iter_edges()
+            .all(|(a, b)| communities[a] == communities[b]));
+    }
+}
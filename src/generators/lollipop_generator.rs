@@ -0,0 +1,104 @@
+use super::{complete_generator, BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::EdgeType;
+use rand::Rng;
+
+/// A factory used to build generators for [lollipop graphs](https://en.wikipedia.org/wiki/Lollipop_graph).
+///
+/// A lollipop graph is made of a complete graph and a path attached to it.
+/// The complete graph is made of the nodes with the lowest labels, followed by the nodes of the path.
+///
+/// In directed graphs generated by this objects, the complete graph has edges in both directions, and the path goes away from the complete graph.
+///
+/// Such factories can be created by passing `lollipop/m,p` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `m` is the number of nodes of the complete graph;
+///   - `p` is the number of nodes in the path (excluding the one of the complete graph).
+///
+/// Parameter `m` must be at least 2.
+#[derive(Default)]
+pub struct LollipopGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for LollipopGeneratorFactory
+where
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "lollipop"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a lollipop graph (a complete graph with a path attached to it).",
+            "First parameter gives the number of nodes of the complete graph, while the second one gives the number of nodes of the path.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a lollipop generator";
+        let m = parameter_values[0].unwrap_usize();
+        let p = parameter_values[1].unwrap_usize();
+        if m < 2 {
+            return Err(anyhow!(r#"first parameter ("m") must be at least 2"#)).context(context);
+        }
+        Ok(Box::new(move |_| {
+            let n = m + p;
+            let mut g = Graph::with_capacity(n, m * (m - 1) + p);
+            (0..n).for_each(|_| g.new_node());
+            complete_generator::add_clique(&mut g, 0..m);
+            (m - 1..n - 1).for_each(|i| g.new_edge(i, i + 1));
+            g
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for LollipopGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+    use petgraph::{Directed, Undirected};
+    use rand::rngs::ThreadRng;
+
+    #[test]
+    fn test_m_is_lower_than_2() {
+        assert!((LollipopGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(0),
+            ParameterValue::PositiveInteger(2)
+        ]) as Result<BoxedGenerator<Undirected, ThreadRng>>)
+            .is_err())
+    }
+
+    #[test]
+    fn test_lollipop() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = LollipopGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::PositiveInteger(2),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(4, g.n_nodes());
+        assert_eq!(
+            vec![(0, 1), (1, 0), (1, 2), (2, 3)],
+            g.iter_edges()
+                .collect::<Vec<(NodeIndexType, NodeIndexType)>>()
+        );
+    }
+}
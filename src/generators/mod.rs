@@ -118,6 +118,10 @@
 //! # }
 //! ```
 //!
+//! Some factories also accept optional parameters, given after the expected ones as `name=value` couples (eg. `config_pl/100,2.5,erase=true`).
+//! They are declared by the `optional_parameters` function, which returns their names, types and default values;
+//! its default implementation returns an empty vector, which is what the path generator needs.
+//!
 //! The only function that is not trivial to implement is `try_with_params`.
 //! Do not fear its return type; it is just either en error or a closure allocated on the heap.
 //! The closure is the generator: given the context built upon the parameters given through the CLI, it produce a graph from a PRNG.
@@ -202,24 +206,24 @@
 //! # use crusti_g2io::{generators::GeneratorFactory, BarabasiAlbertGeneratorFactory, ErdosRenyiGeneratorFactory, TreeGeneratorFactory, WattsStrogatzGeneratorFactory, PathGeneratorFactory};
 //! # use petgraph::{Directed, Undirected};
 //! # use rand_pcg::Pcg32;
-//! # type PathGeneratorFactoryClone = PathGeneratorFactory;
+//! # use crusti_g2io::PathGeneratorFactory as PathGeneratorFactoryClone;
 //! lazy_static! {
 //!     pub(crate) static ref GENERATOR_FACTORIES_DIRECTED_PCG32: [Box<dyn GeneratorFactory<Directed, Pcg32> + Sync>; 5] = [
-//!         Box::new(BarabasiAlbertGeneratorFactory::default()),
-//!         Box::new(ErdosRenyiGeneratorFactory::default()),
-//!         Box::new(TreeGeneratorFactory::default()),
-//!         Box::new(WattsStrogatzGeneratorFactory::default()),
-//!         Box::new(PathGeneratorFactoryClone::default()),
+//!         Box::new(BarabasiAlbertGeneratorFactory),
+//!         Box::new(ErdosRenyiGeneratorFactory),
+//!         Box::new(TreeGeneratorFactory),
+//!         Box::new(WattsStrogatzGeneratorFactory),
+//!         Box::new(PathGeneratorFactoryClone),
 //!     ];
 //! }
 //!
 //! lazy_static! {
 //!     pub(crate) static ref GENERATOR_FACTORIES_UNDIRECTED_PCG32: [Box<dyn GeneratorFactory<Undirected, Pcg32> + Sync>; 5] = [
-//!         Box::new(BarabasiAlbertGeneratorFactory::default()),
-//!         Box::new(ErdosRenyiGeneratorFactory::default()),
-//!         Box::new(TreeGeneratorFactory::default()),
-//!         Box::new(WattsStrogatzGeneratorFactory::default()),
-//!         Box::new(PathGeneratorFactoryClone::default()),
+//!         Box::new(BarabasiAlbertGeneratorFactory),
+//!         Box::new(ErdosRenyiGeneratorFactory),
+//!         Box::new(TreeGeneratorFactory),
+//!         Box::new(WattsStrogatzGeneratorFactory),
+//!         Box::new(PathGeneratorFactoryClone),
 //!     ];
 //! }
 //! ```
@@ -227,18 +231,131 @@
 mod barabasi_albert_generator;
 pub use barabasi_albert_generator::BarabasiAlbertGeneratorFactory;
 
+mod holme_kim;
+pub use holme_kim::HolmeKimGeneratorFactory;
+
 mod path_generator;
 pub use path_generator::PathGeneratorFactory;
 
 mod erdos_renyi;
-pub use erdos_renyi::ErdosRenyiGeneratorFactory;
+pub use erdos_renyi::{ErdosRenyiGeneratorFactory, ErdosRenyiGnmGeneratorFactory};
 
 mod tree_generator;
 pub use tree_generator::TreeGeneratorFactory;
 
+mod random_tree_generator;
+pub use random_tree_generator::{
+    PreferentialAttachmentTreeGeneratorFactory, PruferTreeGeneratorFactory,
+    RandomRecursiveTreeGeneratorFactory,
+};
+
+mod stochastic_block_model;
+pub use stochastic_block_model::{
+    SizedStochasticBlockModelGeneratorFactory, StochasticBlockModelGeneratorFactory,
+};
+
+mod lfr;
+pub use lfr::LfrGeneratorFactory;
+
+mod rmat;
+pub use rmat::{KroneckerGeneratorFactory, RmatGeneratorFactory};
+
+mod random_geometric;
+pub use random_geometric::RandomGeometricGeneratorFactory;
+
+mod waxman;
+pub use waxman::WaxmanGeneratorFactory;
+
+mod kleinberg;
+pub use kleinberg::KleinbergGeneratorFactory;
+
+mod forest_fire;
+pub use forest_fire::ForestFireGeneratorFactory;
+
+mod copying_model;
+pub use copying_model::CopyingModelGeneratorFactory;
+
+mod directed_scale_free;
+pub use directed_scale_free::{DirectedScaleFreeGeneratorFactory, PriceGeneratorFactory};
+
+mod iccma;
+pub use iccma::{
+    IccmaGroundedGeneratorFactory, IccmaSccGeneratorFactory, IccmaStableGeneratorFactory,
+};
+
+mod hyperbolic;
+pub use hyperbolic::HyperbolicGeneratorFactory;
+
+mod chung_lu;
+pub use chung_lu::{
+    ChungLuGeneratorFactory, PoissonChungLuGeneratorFactory, PowerLawChungLuGeneratorFactory,
+    UniformChungLuGeneratorFactory,
+};
+
+mod duplication_divergence;
+pub use duplication_divergence::DuplicationDivergenceGeneratorFactory;
+
 mod watts_strogatz;
 pub use watts_strogatz::WattsStrogatzGeneratorFactory;
 
+mod configuration_model;
+pub use configuration_model::{
+    ConfigurationModelGeneratorFactory, PoissonConfigurationModelGeneratorFactory,
+    PowerLawConfigurationModelGeneratorFactory, UniformConfigurationModelGeneratorFactory,
+};
+
+mod degree_sequences;
+
+mod random_regular;
+pub use random_regular::RandomRegularGeneratorFactory;
+
+mod lattice;
+pub use lattice::{
+    GridGeneratorFactory, HexagonalLatticeGeneratorFactory, HypergridGeneratorFactory,
+    TriangularLatticeGeneratorFactory,
+};
+
+mod complete_generator;
+pub use complete_generator::CompleteGeneratorFactory;
+
+mod cycle_generator;
+pub use cycle_generator::CycleGeneratorFactory;
+
+mod star_generator;
+pub use star_generator::StarGeneratorFactory;
+
+mod wheel_generator;
+pub use wheel_generator::WheelGeneratorFactory;
+
+mod complete_bipartite_generator;
+pub use complete_bipartite_generator::CompleteBipartiteGeneratorFactory;
+
+mod random_bipartite;
+pub use random_bipartite::{RandomBipartiteGeneratorFactory, RandomBipartiteGnmGeneratorFactory};
+
+mod barbell_generator;
+pub use barbell_generator::BarbellGeneratorFactory;
+
+mod lollipop_generator;
+pub use lollipop_generator::LollipopGeneratorFactory;
+
+mod caveman_generator;
+pub use caveman_generator::{
+    ConnectedCavemanGeneratorFactory, RelaxedCavemanGeneratorFactory, RingOfCliquesGeneratorFactory,
+};
+
+mod hypercube_generator;
+pub use hypercube_generator::HypercubeGeneratorFactory;
+
+mod de_bruijn_generator;
+pub use de_bruijn_generator::{DeBruijnGeneratorFactory, KautzGeneratorFactory};
+
+mod tournament;
+pub use tournament::{TournamentGeneratorFactory, TransitiveTournamentGeneratorFactory};
+
+mod dag_generator;
+pub use dag_generator::{LayeredDagGeneratorFactory, RandomDagGeneratorFactory};
+
 use crate::{core::named_param, Graph, NamedParam};
 use anyhow::{Context, Result};
 use lazy_static::lazy_static;
@@ -270,22 +387,117 @@ where
 }
 
 lazy_static! {
-    pub(crate) static ref GENERATOR_FACTORIES_DIRECTED_PCG32: [Box<dyn GeneratorFactory<Directed, Pcg32> + Sync>; 5] = [
-        Box::new(BarabasiAlbertGeneratorFactory::default()),
-        Box::new(PathGeneratorFactory::default()),
-        Box::new(ErdosRenyiGeneratorFactory::default()),
-        Box::new(TreeGeneratorFactory::default()),
-        Box::new(WattsStrogatzGeneratorFactory::default()),
+    pub(crate) static ref GENERATOR_FACTORIES_DIRECTED_PCG32: [Box<dyn GeneratorFactory<Directed, Pcg32> + Sync>; 59] = [
+        Box::new(BarabasiAlbertGeneratorFactory),
+        Box::new(PathGeneratorFactory),
+        Box::new(ErdosRenyiGeneratorFactory),
+        Box::new(TreeGeneratorFactory),
+        Box::new(WattsStrogatzGeneratorFactory),
+        Box::new(StochasticBlockModelGeneratorFactory),
+        Box::new(SizedStochasticBlockModelGeneratorFactory),
+        Box::new(ConfigurationModelGeneratorFactory),
+        Box::new(PowerLawConfigurationModelGeneratorFactory),
+        Box::new(PoissonConfigurationModelGeneratorFactory),
+        Box::new(UniformConfigurationModelGeneratorFactory),
+        Box::new(RandomRegularGeneratorFactory),
+        Box::new(GridGeneratorFactory),
+        Box::new(HypergridGeneratorFactory),
+        Box::new(TriangularLatticeGeneratorFactory),
+        Box::new(HexagonalLatticeGeneratorFactory),
+        Box::new(CompleteGeneratorFactory),
+        Box::new(CycleGeneratorFactory),
+        Box::new(StarGeneratorFactory),
+        Box::new(WheelGeneratorFactory),
+        Box::new(CompleteBipartiteGeneratorFactory),
+        Box::new(BarbellGeneratorFactory),
+        Box::new(LollipopGeneratorFactory),
+        Box::new(PruferTreeGeneratorFactory),
+        Box::new(RandomRecursiveTreeGeneratorFactory),
+        Box::new(PreferentialAttachmentTreeGeneratorFactory),
+        Box::new(RandomDagGeneratorFactory),
+        Box::new(LayeredDagGeneratorFactory),
+        Box::new(HolmeKimGeneratorFactory),
+        Box::new(LfrGeneratorFactory),
+        Box::new(RmatGeneratorFactory),
+        Box::new(KroneckerGeneratorFactory),
+        Box::new(RandomGeometricGeneratorFactory),
+        Box::new(WaxmanGeneratorFactory),
+        Box::new(KleinbergGeneratorFactory),
+        Box::new(ForestFireGeneratorFactory),
+        Box::new(CopyingModelGeneratorFactory),
+        Box::new(PriceGeneratorFactory),
+        Box::new(DirectedScaleFreeGeneratorFactory),
+        Box::new(ErdosRenyiGnmGeneratorFactory),
+        Box::new(HyperbolicGeneratorFactory),
+        Box::new(ChungLuGeneratorFactory),
+        Box::new(PowerLawChungLuGeneratorFactory),
+        Box::new(PoissonChungLuGeneratorFactory),
+        Box::new(UniformChungLuGeneratorFactory),
+        Box::new(IccmaGroundedGeneratorFactory),
+        Box::new(IccmaStableGeneratorFactory),
+        Box::new(IccmaSccGeneratorFactory),
+        Box::new(DuplicationDivergenceGeneratorFactory),
+        Box::new(ConnectedCavemanGeneratorFactory),
+        Box::new(RelaxedCavemanGeneratorFactory),
+        Box::new(RingOfCliquesGeneratorFactory),
+        Box::new(HypercubeGeneratorFactory),
+        Box::new(DeBruijnGeneratorFactory),
+        Box::new(KautzGeneratorFactory),
+        Box::new(RandomBipartiteGeneratorFactory),
+        Box::new(RandomBipartiteGnmGeneratorFactory),
+        Box::new(TournamentGeneratorFactory),
+        Box::new(TransitiveTournamentGeneratorFactory),
     ];
 }
 
 lazy_static! {
-    pub(crate) static ref GENERATOR_FACTORIES_UNDIRECTED_PCG32: [Box<dyn GeneratorFactory<Undirected, Pcg32> + Sync>; 5] = [
-        Box::new(BarabasiAlbertGeneratorFactory::default()),
-        Box::new(PathGeneratorFactory::default()),
-        Box::new(ErdosRenyiGeneratorFactory::default()),
-        Box::new(TreeGeneratorFactory::default()),
-        Box::new(WattsStrogatzGeneratorFactory::default()),
+    pub(crate) static ref GENERATOR_FACTORIES_UNDIRECTED_PCG32: [Box<dyn GeneratorFactory<Undirected, Pcg32> + Sync>; 46] = [
+        Box::new(BarabasiAlbertGeneratorFactory),
+        Box::new(PathGeneratorFactory),
+        Box::new(ErdosRenyiGeneratorFactory),
+        Box::new(TreeGeneratorFactory),
+        Box::new(WattsStrogatzGeneratorFactory),
+        Box::new(StochasticBlockModelGeneratorFactory),
+        Box::new(SizedStochasticBlockModelGeneratorFactory),
+        Box::new(ConfigurationModelGeneratorFactory),
+        Box::new(PowerLawConfigurationModelGeneratorFactory),
+        Box::new(PoissonConfigurationModelGeneratorFactory),
+        Box::new(UniformConfigurationModelGeneratorFactory),
+        Box::new(RandomRegularGeneratorFactory),
+        Box::new(GridGeneratorFactory),
+        Box::new(HypergridGeneratorFactory),
+        Box::new(TriangularLatticeGeneratorFactory),
+        Box::new(HexagonalLatticeGeneratorFactory),
+        Box::new(CompleteGeneratorFactory),
+        Box::new(CycleGeneratorFactory),
+        Box::new(StarGeneratorFactory),
+        Box::new(WheelGeneratorFactory),
+        Box::new(CompleteBipartiteGeneratorFactory),
+        Box::new(BarbellGeneratorFactory),
+        Box::new(LollipopGeneratorFactory),
+        Box::new(PruferTreeGeneratorFactory),
+        Box::new(RandomRecursiveTreeGeneratorFactory),
+        Box::new(PreferentialAttachmentTreeGeneratorFactory),
+        Box::new(HolmeKimGeneratorFactory),
+        Box::new(LfrGeneratorFactory),
+        Box::new(RmatGeneratorFactory),
+        Box::new(KroneckerGeneratorFactory),
+        Box::new(RandomGeometricGeneratorFactory),
+        Box::new(WaxmanGeneratorFactory),
+        Box::new(KleinbergGeneratorFactory),
+        Box::new(ErdosRenyiGnmGeneratorFactory),
+        Box::new(HyperbolicGeneratorFactory),
+        Box::new(ChungLuGeneratorFactory),
+        Box::new(PowerLawChungLuGeneratorFactory),
+        Box::new(PoissonChungLuGeneratorFactory),
+        Box::new(UniformChungLuGeneratorFactory),
+        Box::new(DuplicationDivergenceGeneratorFactory),
+        Box::new(ConnectedCavemanGeneratorFactory),
+        Box::new(RelaxedCavemanGeneratorFactory),
+        Box::new(RingOfCliquesGeneratorFactory),
+        Box::new(HypercubeGeneratorFactory),
+        Box::new(RandomBipartiteGeneratorFactory),
+        Box::new(RandomBipartiteGnmGeneratorFactory),
     ];
 }
 
@@ -0,0 +1,302 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::EdgeType;
+use rand::{
+    distributions::{Distribution, Uniform},
+    seq::index,
+    Rng,
+};
+
+/// A factory used to build generators for random [bipartite graphs](https://en.wikipedia.org/wiki/Bipartite_graph).
+///
+/// The first part is made of the nodes with the lowest labels; each node of the first part is linked to each node of the second part with probability `p`.
+/// The community of each node is set to the index of its part (0 or 1).
+///
+/// In directed graphs generated by this objects, edges go from the first part to the second one.
+/// If the optional parameter `both` is set to `true`, both edges are considered for addition for each pair of nodes, and are drawn independently.
+///
+/// Such factories can be created by passing `bip/n1,n2,p` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n1` is the number of nodes of the first part;
+///   - `n2` is the number of nodes of the second part;
+///   - `p` is the probability each edge appears in the graph.
+///
+/// The optional parameter `both` (eg. `bip/n1,n2,p,both=true`) defaults to `false`, and can only be set for directed graphs.
+#[derive(Default)]
+pub struct RandomBipartiteGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for RandomBipartiteGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "bip"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a random bipartite graph.",
+            "First two parameters give the number of nodes of each part, while the third one gives the probability each edge appears in the graph.",
+            "In directed graphs, edges are also drawn from the second part to the first one if the optional parameter \"both\" is set to true.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+            ParameterType::Probability,
+        ]
+    }
+
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        vec![(
+            "both",
+            ParameterType::Boolean,
+            ParameterValue::Boolean(false),
+        )]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a random bipartite generator";
+        let n1 = parameter_values[0].unwrap_usize();
+        let n2 = parameter_values[1].unwrap_usize();
+        let p = parameter_values[2].unwrap_f64();
+        let both = check_both::<Ty>(parameter_values[3].unwrap_bool()).context(context)?;
+        Ok(Box::new(move |r| {
+            let mut g = init_graph(n1, n2);
+            let proba_uniform = Uniform::new(0., 1.);
+            for i in 0..n1 {
+                for j in n1..n1 + n2 {
+                    if proba_uniform.sample(r) < p {
+                        g.new_edge(i, j);
+                    }
+                    if both && proba_uniform.sample(r) < p {
+                        g.new_edge(j, i);
+                    }
+                }
+            }
+            set_parts(&mut g, n1, n2);
+            g
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for RandomBipartiteGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// A factory used to build generators for random [bipartite graphs](https://en.wikipedia.org/wiki/Bipartite_graph) with a given number of edges.
+///
+/// The first part is made of the nodes with the lowest labels; the edges are chosen uniformly at random among all the possible ones between the two parts.
+/// The community of each node is set to the index of its part (0 or 1).
+///
+/// In directed graphs generated by this objects, edges go from the first part to the second one.
+/// If the optional parameter `both` is set to `true`, the two edges between a pair of nodes are distinct candidates.
+///
+/// Such factories can be created by passing `bip_gnm/n1,n2,m` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n1` is the number of nodes of the first part;
+///   - `n2` is the number of nodes of the second part;
+///   - `m` is the number of edges.
+///
+/// The optional parameter `both` (eg. `bip_gnm/n1,n2,m,both=true`) defaults to `false`, and can only be set for directed graphs.
+/// Parameter `m` cannot exceed the number of possible edges, that is `n1*n2`, or `2*n1*n2` if `both` is set.
+#[derive(Default)]
+pub struct RandomBipartiteGnmGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for RandomBipartiteGnmGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "bip_gnm"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a random bipartite graph with a given number of edges.",
+            "First two parameters give the number of nodes of each part, while the third one gives the number of edges.",
+            "In directed graphs, edges may also go from the second part to the first one if the optional parameter \"both\" is set to true.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+        ]
+    }
+
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        vec![(
+            "both",
+            ParameterType::Boolean,
+            ParameterValue::Boolean(false),
+        )]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a random bipartite generator with a given number of edges";
+        let n1 = parameter_values[0].unwrap_usize();
+        let n2 = parameter_values[1].unwrap_usize();
+        let m = parameter_values[2].unwrap_usize();
+        let both = check_both::<Ty>(parameter_values[3].unwrap_bool()).context(context)?;
+        let max_edges = n1
+            .checked_mul(n2)
+            .and_then(|max| if both { max.checked_mul(2) } else { Some(max) })
+            .ok_or_else(|| anyhow!("too many possible edges"))
+            .context(context)?;
+        if m > max_edges {
+            return Err(anyhow!(
+                r#"third parameter ("m") cannot exceed the number of possible edges ({})"#,
+                max_edges
+            ))
+            .context(context);
+        }
+        Ok(Box::new(move |r| {
+            let mut g = init_graph(n1, n2);
+            let n_pairs = n1 * n2;
+            for e in index::sample(r, max_edges, m) {
+                let (i, j) = (e % n_pairs / n2, n1 + e % n2);
+                if e < n_pairs {
+                    g.new_edge(i, j);
+                } else {
+                    g.new_edge(j, i);
+                }
+            }
+            set_parts(&mut g, n1, n2);
+            g
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for RandomBipartiteGnmGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+fn check_both<Ty>(both: bool) -> Result<bool>
+where
+    Ty: EdgeType,
+{
+    if both && !Ty::is_directed() {
+        return Err(anyhow!(
+            r#"optional parameter "both" is only available for directed graphs"#
+        ));
+    }
+    Ok(both)
+}
+
+fn init_graph<Ty>(n1: usize, n2: usize) -> Graph<Ty>
+where
+    Ty: EdgeType,
+{
+    let mut g = Graph::with_capacity(n1 + n2, 0);
+    (0..n1 + n2).for_each(|_| g.new_node());
+    g
+}
+
+fn set_parts<Ty>(g: &mut Graph<Ty>, n1: usize, n2: usize)
+where
+    Ty: EdgeType,
+{
+    g.set_communities((0..n1 + n2).map(|i| usize::from(i >= n1)).collect());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+    use petgraph::{Directed, Undirected};
+    use rand::rngs::ThreadRng;
+
+    fn params(n1: usize, n2: usize, third: ParameterValue, both: bool) -> Vec<ParameterValue> {
+        vec![
+            ParameterValue::PositiveInteger(n1),
+            ParameterValue::PositiveInteger(n2),
+            third,
+            ParameterValue::Boolean(both),
+        ]
+    }
+
+    fn sorted_edges<Ty>(g: &Graph<Ty>) -> Vec<(NodeIndexType, NodeIndexType)>
+    where
+        Ty: EdgeType,
+    {
+        let mut edges = g
+            .iter_edges()
+            .collect::<Vec<(NodeIndexType, NodeIndexType)>>();
+        edges.sort_unstable();
+        edges
+    }
+
+    #[test]
+    fn test_both_undirected() {
+        assert!((RandomBipartiteGeneratorFactory.try_with_params(params(
+            2,
+            2,
+            ParameterValue::Probability(0.5),
+            true
+        )) as Result<BoxedGenerator<Undirected, ThreadRng>>)
+            .is_err());
+    }
+
+    #[test]
+    fn test_probability_1() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = RandomBipartiteGeneratorFactory
+            .try_with_params(params(2, 1, ParameterValue::Probability(1.), false))
+            .unwrap()(&mut rng);
+        assert_eq!(vec![(0, 2), (1, 2)], sorted_edges(&g));
+        assert_eq!(&[0, 0, 1], g.communities().unwrap());
+        let g: Graph<Directed> = RandomBipartiteGeneratorFactory
+            .try_with_params(params(2, 1, ParameterValue::Probability(1.), true))
+            .unwrap()(&mut rng);
+        assert_eq!(vec![(0, 2), (1, 2), (2, 0), (2, 1)], sorted_edges(&g));
+    }
+
+    #[test]
+    fn test_gnm_too_many_edges() {
+        assert!((RandomBipartiteGnmGeneratorFactory.try_with_params(params(
+            2,
+            3,
+            ParameterValue::PositiveInteger(7),
+            false
+        )) as Result<BoxedGenerator<Directed, ThreadRng>>)
+            .is_err());
+        assert!((RandomBipartiteGnmGeneratorFactory.try_with_params(params(
+            2,
+            3,
+            ParameterValue::PositiveInteger(12),
+            true
+        )) as Result<BoxedGenerator<Directed, ThreadRng>>)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_gnm() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = RandomBipartiteGnmGeneratorFactory
+            .try_with_params(params(4, 5, ParameterValue::PositiveInteger(30), true))
+            .unwrap()(&mut rng);
+        assert_eq!(9, g.n_nodes());
+        let mut edges = sorted_edges(&g);
+        assert!(edges.iter().all(|(a, b)| (*a < 4) != (*b < 4)));
+        edges.dedup();
+        assert_eq!(30, edges.len());
+    }
+}
@@ -0,0 +1,211 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::EdgeType;
+use rand::Rng;
+
+/// A factory used to build generators for [random geometric graphs](https://en.wikipedia.org/wiki/Random_geometric_graph).
+///
+/// Nodes are placed uniformly at random in the unit square, and each pair of nodes within a given (euclidean) distance is linked.
+/// The dimension of the space can be changed by setting the `dim` optional parameter (e.g. `rgg/n,r,dim=3`).
+/// When the optional parameter `torus` is set to `true`, the space is wrapped into a torus, removing boundary effects.
+///
+/// The position of each node is available through [`Graph::positions`].
+///
+/// In directed graphs generated by this objects, edges are set in both directions.
+///
+/// Such factories can be created by passing `rgg/n,r` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the number of nodes;
+///   - `r` is the maximal distance between two linked nodes.
+#[derive(Default)]
+pub struct RandomGeometricGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for RandomGeometricGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "rgg"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing random geometric graphs in the unit square.",
+            "First parameter gives the number of nodes, while the second one gives the maximal distance between linked nodes.",
+            "The dimension is given by the optional parameter \"dim\" (default is 2).",
+            "The space is wrapped into a torus if the optional parameter \"torus\" is set to true.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![ParameterType::PositiveInteger, ParameterType::PositiveFloat]
+    }
+
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        vec![
+            (
+                "dim",
+                ParameterType::PositiveInteger,
+                ParameterValue::PositiveInteger(2),
+            ),
+            (
+                "torus",
+                ParameterType::Boolean,
+                ParameterValue::Boolean(false),
+            ),
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a random geometric graph generator";
+        let n = parameter_values[0].unwrap_usize();
+        let radius = parameter_values[1].unwrap_f64();
+        let dim = parameter_values[2].unwrap_usize();
+        let torus = parameter_values[3].unwrap_bool();
+        if dim == 0 {
+            return Err(anyhow!(r#"optional parameter "dim" must be at least 1"#)).context(context);
+        }
+        Ok(Box::new(move |r| {
+            let positions = (0..n)
+                .map(|_| (0..dim).map(|_| r.gen::<f64>()).collect::<Vec<f64>>())
+                .collect::<Vec<Vec<f64>>>();
+            let mut g = Graph::with_capacity(n, 0);
+            (0..n).for_each(|_| g.new_node());
+            for (i, j) in close_pairs(&positions, radius, torus) {
+                g.new_edge(i, j);
+                if Ty::is_directed() {
+                    g.new_edge(j, i);
+                }
+            }
+            g.set_positions(positions);
+            g
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for RandomGeometricGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// Returns the pairs `(i, j)` with `i < j` of points within the given distance, sorted in lexicographic order.
+///
+/// Points are sorted by their first coordinate, allowing to only compute distances between points that are close on this axis.
+fn close_pairs(positions: &[Vec<f64>], radius: f64, torus: bool) -> Vec<(usize, usize)> {
+    let n = positions.len();
+    let mut sorted = (0..n).collect::<Vec<usize>>();
+    sorted.sort_by(|i, j| positions[*i][0].total_cmp(&positions[*j][0]));
+    let mut pairs = vec![];
+    for (rank, i) in sorted.iter().enumerate() {
+        for offset in 1..n {
+            let j = sorted[(rank + offset) % n];
+            if !torus && rank + offset >= n {
+                break;
+            }
+            let gap = (positions[j][0] - positions[*i][0]).rem_euclid(1.);
+            if gap > radius {
+                break;
+            }
+            let reverse_gap = if gap == 0. { 0. } else { 1. - gap };
+            if torus && (reverse_gap < gap || (reverse_gap == gap && j < *i)) {
+                // the pair is found by scanning from j
+                continue;
+            }
+            if distance(&positions[*i], &positions[j], torus) <= radius {
+                pairs.push((usize::min(*i, j), usize::max(*i, j)));
+            }
+        }
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+fn distance(p0: &[f64], p1: &[f64], torus: bool) -> f64 {
+    p0.iter()
+        .zip(p1.iter())
+        .map(|(x0, x1)| {
+            let d = (x0 - x1).abs();
+            if torus {
+                f64::min(d, 1. - d)
+            } else {
+                d
+            }
+        })
+        .map(|d| d * d)
+        .sum::<f64>()
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use petgraph::{Directed, Undirected};
+    use rand::rngs::ThreadRng;
+
+    fn naive_close_pairs(positions: &[Vec<f64>], radius: f64, torus: bool) -> Vec<(usize, usize)> {
+        let mut pairs = vec![];
+        for i in 0..positions.len() {
+            for j in i + 1..positions.len() {
+                if distance(&positions[i], &positions[j], torus) <= radius {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn test_dim_is_zero() {
+        assert!((RandomGeometricGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(10),
+            ParameterValue::PositiveFloat(0.1),
+            ParameterValue::PositiveInteger(0),
+            ParameterValue::Boolean(false),
+        ]) as Result<BoxedGenerator<Undirected, ThreadRng>>)
+            .is_err())
+    }
+
+    #[test]
+    fn test_close_pairs() {
+        let mut rng = rand::thread_rng();
+        for torus in [false, true] {
+            for radius in [0., 0.1, 0.3, 0.6, 1.5] {
+                let positions = (0..100)
+                    .map(|_| vec![rng.gen::<f64>(), rng.gen::<f64>()])
+                    .collect::<Vec<Vec<f64>>>();
+                assert_eq!(
+                    naive_close_pairs(&positions, radius, torus),
+                    close_pairs(&positions, radius, torus)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_positions() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = RandomGeometricGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(50),
+                ParameterValue::PositiveFloat(0.2),
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::Boolean(true),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(50, g.n_nodes());
+        let positions = g.positions().unwrap();
+        assert!(positions
+            .iter()
+            .all(|p| p.len() == 3 && p.iter().all(|x| (0. ..1.).contains(x))));
+        assert_eq!(
+            2 * naive_close_pairs(positions, 0.2, true).len(),
+            g.n_edges()
+        );
+    }
+}
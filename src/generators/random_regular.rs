@@ -0,0 +1,266 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::EdgeType;
+use rand::{seq::SliceRandom, Rng};
+use std::collections::{BTreeMap, HashSet};
+
+/// A factory used to build generators for random [regular graphs](https://en.wikipedia.org/wiki/Regular_graph).
+///
+/// Graphs are sampled using the algorithm of Steger and Wormald, which produces asymptotically uniform regular graphs without self-loops nor multiple edges.
+/// Stubs (the edge endpoints) are randomly paired; pairs that would produce a self-loop or a multiple edge are discarded and their stubs are paired again.
+/// When the remaining stubs cannot be paired anymore, the generation process starts over.
+///
+/// In directed graphs generated by this objects, each node has both an out-degree and an in-degree equal to `d`.
+///
+/// Such factories can be created by passing `regular/n,d` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the size of graph to produce;
+///   - `d` is the degree of the nodes.
+///
+/// Parameter `n` must be strictly greater than `d` (unless `d` is zero).
+/// For undirected graphs, the product of `n` and `d` must be even.
+#[derive(Default)]
+pub struct RandomRegularGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for RandomRegularGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "regular"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing random regular graphs.",
+            "First parameter gives the number of nodes, while the second one gives the degree of the nodes (both in and out degrees for directed graphs).",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a random regular graph generator";
+        let n = parameter_values[0].unwrap_usize();
+        let d = parameter_values[1].unwrap_usize();
+        if !Ty::is_directed() && (n * d) & 1 == 1 {
+            return Err(anyhow!(
+                r#"the product of the first parameter ("n") and the second one ("d") must be even"#
+            ))
+            .context(context);
+        }
+        if d > 0 && n <= d {
+            return Err(anyhow!(
+                r#"first parameter ("n") must be higher than the second one ("d")"#
+            ))
+            .context(context);
+        }
+        Ok(Box::new(move |r| loop {
+            if let Some(g) = try_build_graph(n, d, r) {
+                return g;
+            }
+        }))
+    }
+}
+
+fn try_build_graph<Ty, R>(n: usize, d: usize, r: &mut R) -> Option<Graph<Ty>>
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    let edge_key = |from: usize, to: usize| {
+        if Ty::is_directed() || from < to {
+            (from, to)
+        } else {
+            (to, from)
+        }
+    };
+    let mut edge_set = HashSet::with_capacity(n * d);
+    let mut edges = Vec::with_capacity(n * d);
+    let all_stubs = (0..n)
+        .flat_map(|i| std::iter::repeat_n(i, d))
+        .collect::<Vec<usize>>();
+    let mut out_stubs = all_stubs.clone();
+    let mut in_stubs = all_stubs;
+    while !out_stubs.is_empty() {
+        let mut remaining_out = BTreeMap::new();
+        let mut remaining_in = BTreeMap::new();
+        let pairs: Vec<(usize, usize)> = if Ty::is_directed() {
+            in_stubs.shuffle(r);
+            out_stubs
+                .iter()
+                .copied()
+                .zip(in_stubs.iter().copied())
+                .collect()
+        } else {
+            out_stubs.shuffle(r);
+            out_stubs.chunks_exact(2).map(|c| (c[0], c[1])).collect()
+        };
+        for (from, to) in pairs {
+            if from != to && edge_set.insert(edge_key(from, to)) {
+                edges.push((from, to));
+            } else {
+                *remaining_out.entry(from).or_insert(0) += 1;
+                *remaining_in.entry(to).or_insert(0) += 1;
+            }
+        }
+        if !Ty::is_directed() {
+            remaining_in.iter().for_each(|(i, k)| {
+                *remaining_out.entry(*i).or_insert(0) += k;
+            });
+            remaining_in = remaining_out.clone();
+        }
+        let suitable = remaining_out.is_empty()
+            || remaining_out.keys().any(|from| {
+                remaining_in
+                    .keys()
+                    .any(|to| from != to && !edge_set.contains(&edge_key(*from, *to)))
+            });
+        if !suitable {
+            return None;
+        }
+        let stubs = |remaining: BTreeMap<usize, usize>| {
+            remaining
+                .into_iter()
+                .flat_map(|(i, k)| std::iter::repeat_n(i, k))
+                .collect::<Vec<usize>>()
+        };
+        out_stubs = stubs(remaining_out);
+        in_stubs = stubs(remaining_in);
+    }
+    let mut g = Graph::with_capacity(n, edges.len());
+    (0..n).for_each(|_| g.new_node());
+    edges
+        .into_iter()
+        .for_each(|(from, to)| g.new_edge(from, to));
+    Some(g)
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for RandomRegularGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use petgraph::{Directed, Undirected};
+    use rand::rngs::ThreadRng;
+
+    fn check_regular<Ty>(g: &Graph<Ty>, n: usize, d: usize)
+    where
+        Ty: EdgeType,
+    {
+        assert_eq!(n, g.n_nodes());
+        let mut out_degrees = vec![0; n];
+        let mut in_degrees = vec![0; n];
+        let mut edges = HashSet::new();
+        g.iter_edges().for_each(|(from, to)| {
+            assert_ne!(from, to);
+            assert!(edges.insert(if Ty::is_directed() || from < to {
+                (from, to)
+            } else {
+                (to, from)
+            }));
+            out_degrees[from] += 1;
+            in_degrees[to] += 1;
+        });
+        if Ty::is_directed() {
+            assert_eq!(vec![d; n], out_degrees);
+            assert_eq!(vec![d; n], in_degrees);
+        } else {
+            let degrees = out_degrees
+                .iter()
+                .zip(in_degrees.iter())
+                .map(|(o, i)| o + i)
+                .collect::<Vec<usize>>();
+            assert_eq!(vec![d; n], degrees);
+        }
+    }
+
+    #[test]
+    fn test_odd_product() {
+        assert!((RandomRegularGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(5),
+            ParameterValue::PositiveInteger(3),
+        ]) as Result<BoxedGenerator<Undirected, ThreadRng>>)
+            .is_err())
+    }
+
+    #[test]
+    fn test_odd_product_directed() {
+        assert!((RandomRegularGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(5),
+            ParameterValue::PositiveInteger(3),
+        ]) as Result<BoxedGenerator<Directed, ThreadRng>>)
+            .is_ok())
+    }
+
+    #[test]
+    fn test_n_is_not_higher_than_d() {
+        assert!((RandomRegularGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(4),
+            ParameterValue::PositiveInteger(4),
+        ]) as Result<BoxedGenerator<Directed, ThreadRng>>)
+            .is_err())
+    }
+
+    #[test]
+    fn test_undirected() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = RandomRegularGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(20),
+                ParameterValue::PositiveInteger(3),
+            ])
+            .unwrap()(&mut rng);
+        check_regular(&g, 20, 3);
+    }
+
+    #[test]
+    fn test_directed() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = RandomRegularGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(7),
+                ParameterValue::PositiveInteger(3),
+            ])
+            .unwrap()(&mut rng);
+        check_regular(&g, 7, 3);
+    }
+
+    #[test]
+    fn test_complete() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = RandomRegularGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(5),
+                ParameterValue::PositiveInteger(4),
+            ])
+            .unwrap()(&mut rng);
+        check_regular(&g, 5, 4);
+    }
+
+    #[test]
+    fn test_degree_zero() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = RandomRegularGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::PositiveInteger(0),
+            ])
+            .unwrap()(&mut rng);
+        check_regular(&g, 3, 0);
+    }
+}
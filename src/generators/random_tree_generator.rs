@@ -0,0 +1,288 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::Result;
+use petgraph::EdgeType;
+use rand::Rng;
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+/// A factory used to build generators for uniform random labeled trees.
+///
+/// Trees are built by decoding a random [Prüfer sequence](https://en.wikipedia.org/wiki/Pr%C3%BCfer_sequence),
+/// which makes all the labeled trees on `n` nodes equiprobable.
+///
+/// In directed graphs generated by this objects, the node labeled 0 is the root and all edges follow paths from the root to the leaves.
+///
+/// Such factories can be created by passing `tree_prufer/n` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str),
+/// where `n` is the number of node.
+#[derive(Default)]
+pub struct PruferTreeGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for PruferTreeGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "tree_prufer"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a uniform random labeled tree, using Prüfer sequences.",
+            "The first parameter gives the number of nodes.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![ParameterType::PositiveInteger]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let n = parameter_values[0].unwrap_usize();
+        Ok(Box::new(move |r| {
+            if n < 2 {
+                let mut g = Graph::with_capacity(n, 0);
+                (0..n).for_each(|_| g.new_node());
+                return g;
+            }
+            let sequence = (0..n - 2)
+                .map(|_| r.gen_range(0..n))
+                .collect::<Vec<usize>>();
+            let mut neighbors = vec![vec![]; n];
+            prufer_decode(n, &sequence).into_iter().for_each(|(a, b)| {
+                neighbors[a].push(b);
+                neighbors[b].push(a);
+            });
+            let mut g = Graph::with_capacity(n, n - 1);
+            (0..n).for_each(|_| g.new_node());
+            let mut visited = vec![false; n];
+            visited[0] = true;
+            let mut queue = vec![0];
+            let mut next = 0;
+            while next < queue.len() {
+                let current = queue[next];
+                next += 1;
+                for &neighbor in neighbors[current].iter() {
+                    if !visited[neighbor] {
+                        visited[neighbor] = true;
+                        g.new_edge(current, neighbor);
+                        queue.push(neighbor);
+                    }
+                }
+            }
+            g
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for PruferTreeGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// Decodes a Prüfer sequence into the (undirected) edges of the corresponding tree on `n` nodes.
+fn prufer_decode(n: usize, sequence: &[usize]) -> Vec<(usize, usize)> {
+    let mut degrees = vec![1; n];
+    sequence.iter().for_each(|&i| degrees[i] += 1);
+    let mut leaves = (0..n)
+        .filter(|&i| degrees[i] == 1)
+        .map(Reverse)
+        .collect::<BinaryHeap<Reverse<usize>>>();
+    let mut edges = Vec::with_capacity(n - 1);
+    for &i in sequence {
+        let Reverse(leaf) = leaves.pop().unwrap();
+        edges.push((leaf, i));
+        degrees[i] -= 1;
+        if degrees[i] == 1 {
+            leaves.push(Reverse(i));
+        }
+    }
+    let Reverse(a) = leaves.pop().unwrap();
+    let Reverse(b) = leaves.pop().unwrap();
+    edges.push((a, b));
+    edges
+}
+
+/// A factory used to build generators for [random recursive trees](https://en.wikipedia.org/wiki/Recursive_tree).
+///
+/// Nodes are added one at a time, each new node being linked to a node chosen uniformly at random among the previous ones.
+///
+/// In directed graphs generated by this objects, the node labeled 0 is the root and all edges follow paths from the root to the leaves.
+///
+/// Such factories can be created by passing `tree_rrt/n` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str),
+/// where `n` is the number of node.
+#[derive(Default)]
+pub struct RandomRecursiveTreeGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for RandomRecursiveTreeGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "tree_rrt"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a random recursive tree.",
+            "The first parameter gives the number of nodes.",
+            "Each new node is linked to a uniformly chosen previous node.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![ParameterType::PositiveInteger]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let n = parameter_values[0].unwrap_usize();
+        Ok(Box::new(move |r| {
+            let mut g = Graph::with_capacity(n, n.saturating_sub(1));
+            (0..n).for_each(|_| g.new_node());
+            (1..n).for_each(|i| g.new_edge(r.gen_range(0..i), i));
+            g
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for RandomRecursiveTreeGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// A factory used to build generators for preferential attachment trees.
+///
+/// Nodes are added one at a time, each new node being linked to a previous node chosen with a probability proportional to its degree.
+/// This is the Barabási-Albert model in which each new node brings a single edge.
+///
+/// In directed graphs generated by this objects, the node labeled 0 is the root and all edges follow paths from the root to the leaves.
+///
+/// Such factories can be created by passing `tree_pa/n` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str),
+/// where `n` is the number of node.
+#[derive(Default)]
+pub struct PreferentialAttachmentTreeGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for PreferentialAttachmentTreeGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "tree_pa"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a preferential attachment tree.",
+            "The first parameter gives the number of nodes.",
+            "Each new node is linked to a previous node chosen with a probability proportional to its degree.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![ParameterType::PositiveInteger]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let n = parameter_values[0].unwrap_usize();
+        Ok(Box::new(move |r| {
+            let mut g = Graph::with_capacity(n, n.saturating_sub(1));
+            (0..n).for_each(|_| g.new_node());
+            let mut endpoints = Vec::with_capacity(2 * n.saturating_sub(1));
+            for i in 1..n {
+                let parent = if i == 1 {
+                    0
+                } else {
+                    endpoints[r.gen_range(0..endpoints.len())]
+                };
+                g.new_edge(parent, i);
+                endpoints.push(parent);
+                endpoints.push(i);
+            }
+            g
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for PreferentialAttachmentTreeGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use petgraph::Directed;
+
+    fn assert_is_out_tree(g: &Graph<Directed>, n: usize) {
+        assert_eq!(n, g.n_nodes());
+        assert_eq!(n.saturating_sub(1), g.n_edges());
+        let mut children = vec![vec![]; n];
+        g.iter_edges().for_each(|(a, b)| children[a].push(b));
+        let mut visited = vec![false; n];
+        let mut to_visit = (0..n).take(1).collect::<Vec<usize>>();
+        while let Some(i) = to_visit.pop() {
+            assert!(!visited[i]);
+            visited[i] = true;
+            to_visit.append(&mut children[i]);
+        }
+        assert!(visited.into_iter().all(|v| v));
+    }
+
+    #[test]
+    fn test_prufer_decode() {
+        assert_eq!(
+            vec![(0, 3), (1, 3), (2, 3), (3, 4), (4, 5)],
+            prufer_decode(6, &[3, 3, 3, 4])
+        );
+    }
+
+    #[test]
+    fn test_prufer_tree() {
+        let mut rng = rand::thread_rng();
+        for n in [0, 1, 2, 10] {
+            let g: Graph<Directed> = PruferTreeGeneratorFactory
+                .try_with_params(vec![ParameterValue::PositiveInteger(n)])
+                .unwrap()(&mut rng);
+            assert_is_out_tree(&g, n);
+        }
+    }
+
+    #[test]
+    fn test_random_recursive_tree() {
+        let mut rng = rand::thread_rng();
+        for n in [0, 1, 2, 10] {
+            let g: Graph<Directed> = RandomRecursiveTreeGeneratorFactory
+                .try_with_params(vec![ParameterValue::PositiveInteger(n)])
+                .unwrap()(&mut rng);
+            assert_is_out_tree(&g, n);
+        }
+    }
+
+    #[test]
+    fn test_preferential_attachment_tree() {
+        let mut rng = rand::thread_rng();
+        for n in [0, 1, 2, 10] {
+            let g: Graph<Directed> = PreferentialAttachmentTreeGeneratorFactory
+                .try_with_params(vec![ParameterValue::PositiveInteger(n)])
+                .unwrap()(&mut rng);
+            assert_is_out_tree(&g, n);
+        }
+    }
+}
@@ -0,0 +1,339 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::EdgeType;
+use rand::Rng;
+use std::collections::HashSet;
+
+/// A factory used to build generators for [R-MAT](https://doi.org/10.1137/1.9781611972740.43) graphs.
+///
+/// The graphs have `2^scale` nodes.
+/// Each edge is placed by recursively choosing one of the four quadrants of the adjacency matrix,
+/// with respective probabilities `a`, `b`, `c` and `1-a-b-c`.
+/// Self-loops and multiple edges are discarded, so the number of edges may be lower than expected.
+///
+/// In undirected graphs generated by this objects, the edges falling in the lower and upper parts of the adjacency matrix are merged.
+///
+/// Such factories can be created by passing `rmat/scale,ef,a,b,c` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `scale` is the base 2 logarithm of the number of nodes;
+///   - `ef` is the edge factor, i.e. the number of edges to draw per node;
+///   - `a`, `b` and `c` are the probabilities of the top-left, top-right and bottom-left quadrants.
+///
+/// The sum of `a`, `b` and `c` must not exceed 1.
+/// Graph500 benchmarks use `rmat/s,16,0.57,0.19,0.19`.
+#[derive(Default)]
+pub struct RmatGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for RmatGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "rmat"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the R-MAT model.",
+            "First parameter gives the base 2 logarithm of the number of nodes, while the second one gives the number of edges to draw per node.",
+            "The last three parameters give the probabilities of the top-left, top-right and bottom-left quadrants of the adjacency matrix.",
+            "Self-loops and multiple edges are discarded.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+            ParameterType::Probability,
+            ParameterType::Probability,
+            ParameterType::Probability,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a R-MAT generator";
+        let scale = parameter_values[0].unwrap_usize();
+        let edge_factor = parameter_values[1].unwrap_usize();
+        let a = parameter_values[2].unwrap_f64();
+        let b = parameter_values[3].unwrap_f64();
+        let c = parameter_values[4].unwrap_f64();
+        let n = n_nodes(scale).context(context)?;
+        let m = n
+            .checked_mul(edge_factor)
+            .ok_or_else(|| anyhow!("too many edges"))
+            .context(context)?;
+        if a + b + c > 1. + 1e-9 {
+            return Err(anyhow!(
+                r#"the sum of the quadrant probabilities ("a", "b" and "c") must not exceed 1"#
+            ))
+            .context(context);
+        }
+        Ok(Box::new(move |r| {
+            build_graph(scale, m, [a, a + b, a + b + c].map(|t| t.min(1.)), r)
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for RmatGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// A factory used to build generators for [stochastic Kronecker](https://arxiv.org/abs/0812.4905) graphs.
+///
+/// The graphs have `2^k` nodes, and are defined by a 2x2 initiator matrix of probabilities `[[a, b], [c, d]]`.
+/// The probability of an edge is the product of the initiator entries given by the bits of the labels of its nodes;
+/// the expected number of edges is thus `(a+b+c+d)^k`.
+///
+/// In order to handle large graphs, this number of edges is drawn by recursively choosing quadrants of the adjacency matrix,
+/// with probabilities proportional to the initiator entries (just like the [`RmatGeneratorFactory`] does).
+/// Self-loops and multiple edges are discarded, so the number of edges may be lower than expected.
+///
+/// In undirected graphs generated by this objects, the edges falling in the lower and upper parts of the adjacency matrix are merged.
+///
+/// Such factories can be created by passing `kron/k,a,b,c,d` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `k` is the number of Kronecker products, i.e. the base 2 logarithm of the number of nodes;
+///   - `a`, `b`, `c` and `d` are the entries of the initiator matrix.
+#[derive(Default)]
+pub struct KroneckerGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for KroneckerGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "kron"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the stochastic Kronecker model with a 2x2 initiator matrix.",
+            "First parameter gives the number of Kronecker products (the base 2 logarithm of the number of nodes).",
+            "The last four parameters give the entries of the initiator matrix, row by row.",
+            "Self-loops and multiple edges are discarded.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::Probability,
+            ParameterType::Probability,
+            ParameterType::Probability,
+            ParameterType::Probability,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a stochastic Kronecker generator";
+        let k = parameter_values[0].unwrap_usize();
+        let a = parameter_values[1].unwrap_f64();
+        let b = parameter_values[2].unwrap_f64();
+        let c = parameter_values[3].unwrap_f64();
+        let d = parameter_values[4].unwrap_f64();
+        n_nodes(k).context(context)?;
+        let sum = a + b + c + d;
+        let expected_m = sum.powi(k as i32).round();
+        if expected_m >= usize::MAX as f64 {
+            return Err(anyhow!("too many edges")).context(context);
+        }
+        let m = expected_m as usize;
+        let thresholds = if sum > 0. {
+            [a / sum, (a + b) / sum, (a + b + c) / sum]
+        } else {
+            [0.; 3]
+        };
+        Ok(Box::new(move |r| build_graph(k, m, thresholds, r)))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for KroneckerGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+fn n_nodes(scale: usize) -> Result<usize> {
+    u32::try_from(scale)
+        .ok()
+        .and_then(|s| 1_usize.checked_shl(s))
+        .ok_or_else(|| anyhow!("too many nodes"))
+}
+
+/// Builds a graph with `2^scale` nodes by drawing `m` edges in the adjacency matrix.
+///
+/// The thresholds are the cumulated probabilities of the first three quadrants.
+fn build_graph<Ty, R>(scale: usize, m: usize, thresholds: [f64; 3], r: &mut R) -> Graph<Ty>
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    let n = 1 << scale;
+    let mut edges = HashSet::with_capacity(m);
+    let mut g = Graph::with_capacity(n, m);
+    (0..n).for_each(|_| g.new_node());
+    for _ in 0..m {
+        let (mut from, mut to) = (0, 0);
+        for _ in 0..scale {
+            let p: f64 = r.gen();
+            let (from_bit, to_bit) = if p < thresholds[0] {
+                (0, 0)
+            } else if p < thresholds[1] {
+                (0, 1)
+            } else if p < thresholds[2] {
+                (1, 0)
+            } else {
+                (1, 1)
+            };
+            from = (from << 1) | from_bit;
+            to = (to << 1) | to_bit;
+        }
+        if from == to {
+            continue;
+        }
+        let key = if Ty::is_directed() {
+            (from, to)
+        } else {
+            (usize::min(from, to), usize::max(from, to))
+        };
+        if edges.insert(key) {
+            g.new_edge(from, to);
+        }
+    }
+    g
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+    use petgraph::{Directed, Undirected};
+    use rand::rngs::ThreadRng;
+
+    #[test]
+    fn test_rmat_probabilities_too_high() {
+        assert!((RmatGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(4),
+            ParameterValue::PositiveInteger(4),
+            ParameterValue::Probability(0.5),
+            ParameterValue::Probability(0.3),
+            ParameterValue::Probability(0.3),
+        ]) as Result<BoxedGenerator<Directed, ThreadRng>>)
+            .is_err())
+    }
+
+    #[test]
+    fn test_rmat_rounded_probabilities() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = RmatGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::Probability(0.33),
+                ParameterValue::Probability(0.56),
+                ParameterValue::Probability(0.11),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(8, g.n_nodes());
+        assert!(g.iter_edges().all(|(a, b)| a & b == 0));
+    }
+
+    #[test]
+    fn test_rmat_scale_too_high() {
+        assert!((RmatGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(64),
+            ParameterValue::PositiveInteger(1),
+            ParameterValue::Probability(0.25),
+            ParameterValue::Probability(0.25),
+            ParameterValue::Probability(0.25),
+        ]) as Result<BoxedGenerator<Directed, ThreadRng>>)
+            .is_err())
+    }
+
+    #[test]
+    fn test_rmat_single_quadrant() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = RmatGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::Probability(0.),
+                ParameterValue::Probability(1.),
+                ParameterValue::Probability(0.),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(8, g.n_nodes());
+        assert_eq!(
+            vec![(0, 7)],
+            g.iter_edges()
+                .collect::<Vec<(NodeIndexType, NodeIndexType)>>()
+        );
+    }
+
+    #[test]
+    fn test_rmat_simple_graph() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = RmatGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(10),
+                ParameterValue::PositiveInteger(16),
+                ParameterValue::Probability(0.57),
+                ParameterValue::Probability(0.19),
+                ParameterValue::Probability(0.19),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(1024, g.n_nodes());
+        assert!(g.n_edges() <= 16 * 1024);
+        let edges = g
+            .iter_edges()
+            .map(|(a, b)| (usize::min(a, b), usize::max(a, b)))
+            .collect::<HashSet<(usize, usize)>>();
+        assert_eq!(g.n_edges(), edges.len());
+        assert!(edges.iter().all(|(a, b)| a != b));
+    }
+
+    #[test]
+    fn test_kronecker_null_initiator() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = KroneckerGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::Probability(0.),
+                ParameterValue::Probability(0.),
+                ParameterValue::Probability(0.),
+                ParameterValue::Probability(0.),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(8, g.n_nodes());
+        assert_eq!(0, g.n_edges());
+    }
+
+    #[test]
+    fn test_kronecker() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = KroneckerGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::Probability(0.),
+                ParameterValue::Probability(1.),
+                ParameterValue::Probability(1.),
+                ParameterValue::Probability(0.),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(4, g.n_nodes());
+        assert!(g.n_edges() <= 4);
+        assert!(g.iter_edges().all(|(a, b)| a ^ b == 3));
+    }
+}
@@ -0,0 +1,332 @@
+use super::{degree_sequences, BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::EdgeType;
+use rand::{distributions::Uniform, prelude::Distribution, Rng};
+
+/// A factory used to build generators for graphs following the [stochastic block model](https://en.wikipedia.org/wiki/Stochastic_block_model).
+///
+/// The nodes are split into blocks, the first block containing the nodes with the lowest labels.
+/// Each pair of nodes is linked with a probability that depends on whether the nodes belong to the same block or not.
+/// The block of each node is available through [`Graph::communities`].
+///
+/// In directed graphs generated by this objects, for each pair of nodes, both edges are considered for addition (0, 1 or 2 edges can be generated).
+///
+/// Such factories can be created by passing `sbm/k,s,p,q` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `k` is the number of blocks;
+///   - `s` is the number of nodes in each block;
+///   - `p` is the probability each edge appears between two nodes of the same block;
+///   - `q` is the probability each edge appears between two nodes of different blocks.
+///
+/// Parameters `k` and `s` must be positive, while `p` and `q` must be floating point numbers between 0 and 1.
+///
+/// The optional parameter `smax` (eg. `sbm/k,s,p,q,smax=10`) makes the size of each block be drawn uniformly between `s` and `smax`;
+/// its default value, 0, means all the blocks have size `s`.
+/// When it is set, `smax` must not be lower than `s`.
+///
+/// See [`SizedStochasticBlockModelGeneratorFactory`] to give an explicit size for each block.
+#[derive(Default)]
+pub struct StochasticBlockModelGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for StochasticBlockModelGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "sbm"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the stochastic block model.",
+            "First parameter gives the number of blocks, while the second one gives the number of nodes in each block.",
+            "Block sizes are drawn between the second parameter and the optional parameter \"smax\" if the latter is set.",
+            "The third parameter gives the probability each edge appears inside a block, and the fourth one the probability each edge appears between two blocks.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveInteger,
+            ParameterType::Probability,
+            ParameterType::Probability,
+        ]
+    }
+
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        vec![(
+            "smax",
+            ParameterType::PositiveInteger,
+            ParameterValue::PositiveInteger(0),
+        )]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a stochastic block model generator";
+        let n_blocks = parameter_values[0].unwrap_usize();
+        let block_size = parameter_values[1].unwrap_usize();
+        let p_intra = parameter_values[2].unwrap_f64();
+        let p_inter = parameter_values[3].unwrap_f64();
+        let max_block_size = match parameter_values[4].unwrap_usize() {
+            0 => block_size,
+            s_max if s_max < block_size => {
+                return Err(anyhow!(
+                    r#"optional parameter "smax" must not be lower than the second one ("s")"#
+                ))
+                .context(context)
+            }
+            s_max => s_max,
+        };
+        Ok(Box::new(move |r| {
+            let blocks = (0..n_blocks)
+                .flat_map(|b| {
+                    let size = r.gen_range(block_size..=max_block_size);
+                    std::iter::repeat_n(b, size)
+                })
+                .collect::<Vec<usize>>();
+            build_graph(blocks, p_intra, p_inter, r)
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for StochasticBlockModelGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// A factory used to build generators for graphs following the [stochastic block model](https://en.wikipedia.org/wiki/Stochastic_block_model), given block sizes read from a file.
+///
+/// Each non-empty line of the file must contain the size of a block; lines beginning with a `#` are ignored.
+/// The sizes are read once, when the factory builds the generator.
+///
+/// See [`StochasticBlockModelGeneratorFactory`] for more information on the generation process.
+///
+/// Such factories can be created by passing `sbm_file/f,p,q` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `f` is the path to the file containing the block sizes;
+///   - `p` is the probability each edge appears between two nodes of the same block;
+///   - `q` is the probability each edge appears between two nodes of different blocks.
+#[derive(Default)]
+pub struct SizedStochasticBlockModelGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for SizedStochasticBlockModelGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "sbm_file"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the stochastic block model, using block sizes read from a file.",
+            "The first parameter is the path to the file; each line contains the size of a block.",
+            "The second parameter gives the probability each edge appears inside a block, and the third one the probability each edge appears between two blocks.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::String,
+            ParameterType::Probability,
+            ParameterType::Probability,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a stochastic block model generator";
+        let path = parameter_values[0].unwrap_str();
+        let p_intra = parameter_values[1].unwrap_f64();
+        let p_inter = parameter_values[2].unwrap_f64();
+        let sizes = degree_sequences::read_columns::<usize>(path, 1)
+            .context(context)?
+            .pop()
+            .unwrap();
+        let blocks = sizes
+            .iter()
+            .enumerate()
+            .flat_map(|(b, size)| std::iter::repeat_n(b, *size))
+            .collect::<Vec<usize>>();
+        Ok(Box::new(move |r| {
+            build_graph(blocks.clone(), p_intra, p_inter, r)
+        }))
+    }
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for SizedStochasticBlockModelGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+/// Builds a graph given the block of each node.
+fn build_graph<Ty, R>(blocks: Vec<usize>, p_intra: f64, p_inter: f64, r: &mut R) -> Graph<Ty>
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    let n = blocks.len();
+    let mut g = Graph::with_capacity(n, 0);
+    (0..n).for_each(|_| g.new_node());
+    let proba_uniform = Uniform::new_inclusive(0., 1.);
+    for i in 0..n {
+        let first_candidate = if Ty::is_directed() { 0 } else { i + 1 };
+        for j in first_candidate..n {
+            if i == j {
+                continue;
+            }
+            let p = if blocks[i] == blocks[j] {
+                p_intra
+            } else {
+                p_inter
+            };
+            if proba_uniform.sample(r) < p {
+                g.new_edge(i, j);
+            }
+        }
+    }
+    g.set_communities(blocks);
+    g
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+    use petgraph::{Directed, Undirected};
+    use rand::rngs::ThreadRng;
+
+    #[test]
+    fn test_no_blocks() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = StochasticBlockModelGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(0),
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::Probability(1.0),
+                ParameterValue::Probability(1.0),
+                ParameterValue::PositiveInteger(0),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(0, g.n_nodes());
+        assert_eq!(0, g.n_edges());
+    }
+
+    #[test]
+    fn test_disconnected_blocks() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = StochasticBlockModelGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::PositiveInteger(3),
+                ParameterValue::Probability(1.0),
+                ParameterValue::Probability(0.0),
+                ParameterValue::PositiveInteger(0),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(6, g.n_nodes());
+        let mut edges = g
+            .iter_edges()
+            .collect::<Vec<(NodeIndexType, NodeIndexType)>>();
+        edges.sort_unstable();
+        assert_eq!(vec![(0, 1), (0, 2), (1, 2), (3, 4), (3, 5), (4, 5)], edges);
+        assert_eq!(Some([0, 0, 0, 1, 1, 1].as_slice()), g.communities());
+    }
+
+    #[test]
+    fn test_inter_blocks_only() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = StochasticBlockModelGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::PositiveInteger(1),
+                ParameterValue::Probability(0.0),
+                ParameterValue::Probability(1.0),
+                ParameterValue::PositiveInteger(0),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(2, g.n_nodes());
+        let mut edges = g
+            .iter_edges()
+            .collect::<Vec<(NodeIndexType, NodeIndexType)>>();
+        edges.sort_unstable();
+        assert_eq!(vec![(0, 1), (1, 0)], edges);
+    }
+
+    #[test]
+    fn test_max_size_lower_than_size() {
+        assert!((StochasticBlockModelGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(2),
+            ParameterValue::PositiveInteger(3),
+            ParameterValue::Probability(1.0),
+            ParameterValue::Probability(0.0),
+            ParameterValue::PositiveInteger(2),
+        ]) as Result<BoxedGenerator<Undirected, ThreadRng>>)
+            .is_err());
+    }
+
+    #[test]
+    fn test_variable_sizes() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = StochasticBlockModelGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(10),
+                ParameterValue::PositiveInteger(2),
+                ParameterValue::Probability(1.0),
+                ParameterValue::Probability(0.0),
+                ParameterValue::PositiveInteger(5),
+            ])
+            .unwrap()(&mut rng);
+        let communities = g.communities().unwrap();
+        assert_eq!(g.n_nodes(), communities.len());
+        let mut sizes = [0; 10];
+        communities.iter().for_each(|c| sizes[*c] += 1);
+        assert!(sizes.iter().all(|s| (2..=5).contains(s)));
+        assert!(communities.windows(2).all(|w| w[0] <= w[1]));
+        let expected_edges = sizes.iter().map(|s| s * (s - 1) / 2).sum::<usize>();
+        assert_eq!(expected_edges, g.n_edges());
+    }
+
+    #[test]
+    fn test_sizes_file() {
+        let path = std::env::temp_dir().join(format!(
+            "crusti_g2io_test_sbm_file_{}.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, "# sizes\n1\n3\n\n2\n").unwrap();
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = SizedStochasticBlockModelGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::String(path.to_str().unwrap().to_string()),
+                ParameterValue::Probability(1.0),
+                ParameterValue::Probability(0.0),
+            ])
+            .unwrap()(&mut rng);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(Some([0, 1, 1, 1, 2, 2].as_slice()), g.communities());
+        assert_eq!(4, g.n_edges());
+    }
+
+    #[test]
+    fn test_sizes_missing_file() {
+        assert!(
+            (SizedStochasticBlockModelGeneratorFactory.try_with_params(vec![
+                ParameterValue::String("/this/file/does/not/exist".to_string()),
+                ParameterValue::Probability(1.0),
+                ParameterValue::Probability(0.0),
+            ]) as Result<BoxedGenerator<Undirected, ThreadRng>>)
+                .is_err()
+        );
+    }
+}
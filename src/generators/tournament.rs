@@ -0,0 +1,177 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::Result;
+use petgraph::Directed;
+use rand::{
+    distributions::{Distribution, Uniform},
+    Rng,
+};
+
+/// A factory used to build generators for random [tournaments](https://en.wikipedia.org/wiki/Tournament_(graph_theory)).
+///
+/// Each pair of distinct nodes is linked by exactly one edge, whose direction is chosen uniformly at random.
+///
+/// This generator is only available for directed graphs.
+///
+/// Such factories can be created by passing `tournament/n` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str),
+/// where `n` is the number of nodes.
+#[derive(Default)]
+pub struct TournamentGeneratorFactory;
+
+impl<R> NamedParam<BoxedGenerator<Directed, R>> for TournamentGeneratorFactory
+where
+    R: Rng,
+{
+    fn name(&self) -> &'static str {
+        "tournament"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a random tournament.",
+            "The parameter gives the number of nodes.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![ParameterType::PositiveInteger]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Directed, R>> {
+        let n = parameter_values[0].unwrap_usize();
+        Ok(Box::new(move |r| build_tournament(n, 0.5, r)))
+    }
+}
+
+impl<R> GeneratorFactory<Directed, R> for TournamentGeneratorFactory where R: Rng {}
+
+/// A factory used to build generators for transitive [tournaments](https://en.wikipedia.org/wiki/Tournament_(graph_theory)).
+///
+/// Each pair of distinct nodes is linked by exactly one edge, going from the node with the lowest label to the other one.
+/// The resulting graph is acyclic.
+/// The optional parameter `upset` gives the probability each edge is reversed, which introduces 3-cycles;
+/// an upset probability of `0.5` produces a uniform random tournament.
+///
+/// This generator is only available for directed graphs.
+///
+/// Such factories can be created by passing `transitive_tournament/n` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str),
+/// where `n` is the number of nodes.
+///
+/// The optional parameter `upset` (eg. `transitive_tournament/n,upset=0.1`) defaults to `0`.
+#[derive(Default)]
+pub struct TransitiveTournamentGeneratorFactory;
+
+impl<R> NamedParam<BoxedGenerator<Directed, R>> for TransitiveTournamentGeneratorFactory
+where
+    R: Rng,
+{
+    fn name(&self) -> &'static str {
+        "transitive_tournament"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator producing a transitive tournament.",
+            "The parameter gives the number of nodes.",
+            "Each edge is reversed with the probability given by the optional parameter \"upset\".",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![ParameterType::PositiveInteger]
+    }
+
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        vec![(
+            "upset",
+            ParameterType::Probability,
+            ParameterValue::Probability(0.),
+        )]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Directed, R>> {
+        let n = parameter_values[0].unwrap_usize();
+        let upset = parameter_values[1].unwrap_f64();
+        Ok(Box::new(move |r| build_tournament(n, upset, r)))
+    }
+}
+
+impl<R> GeneratorFactory<Directed, R> for TransitiveTournamentGeneratorFactory where R: Rng {}
+
+fn build_tournament<R>(n: usize, p_reverse: f64, r: &mut R) -> Graph<Directed>
+where
+    R: Rng,
+{
+    let mut g = Graph::with_capacity(n, n * n.saturating_sub(1) / 2);
+    (0..n).for_each(|_| g.new_node());
+    let proba_uniform = Uniform::new(0., 1.);
+    for i in 0..n {
+        for j in i + 1..n {
+            if proba_uniform.sample(r) < p_reverse {
+                g.new_edge(j, i);
+            } else {
+                g.new_edge(i, j);
+            }
+        }
+    }
+    g
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeIndexType;
+    use std::collections::HashSet;
+
+    fn assert_tournament(n: usize, g: &Graph<Directed>) {
+        assert_eq!(n, g.n_nodes());
+        assert_eq!(n * (n - 1) / 2, g.n_edges());
+        let pairs = g
+            .iter_edges()
+            .map(|(a, b)| (a.min(b), a.max(b)))
+            .collect::<HashSet<(NodeIndexType, NodeIndexType)>>();
+        assert_eq!(g.n_edges(), pairs.len());
+        assert!(pairs.iter().all(|(a, b)| a != b));
+    }
+
+    #[test]
+    fn test_tournament() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = TournamentGeneratorFactory
+            .try_with_params(vec![ParameterValue::PositiveInteger(10)])
+            .unwrap()(&mut rng);
+        assert_tournament(10, &g);
+    }
+
+    #[test]
+    fn test_transitive_tournament() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = TransitiveTournamentGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(10),
+                ParameterValue::Probability(0.),
+            ])
+            .unwrap()(&mut rng);
+        assert_tournament(10, &g);
+        assert!(g.iter_edges().all(|(a, b)| a < b));
+    }
+
+    #[test]
+    fn test_full_upset() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = TransitiveTournamentGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(10),
+                ParameterValue::Probability(1.),
+            ])
+            .unwrap()(&mut rng);
+        assert_tournament(10, &g);
+        assert!(g.iter_edges().all(|(a, b)| a > b));
+    }
+}
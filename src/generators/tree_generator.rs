@@ -1,15 +1,19 @@
 use super::{BoxedGenerator, GeneratorFactory};
 use crate::{Graph, NamedParam, ParameterType, ParameterValue};
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use petgraph::EdgeType;
 use rand::Rng;
 
 /// A factory used to build generators for balanced trees.
 ///
+/// Nodes are labeled in breadth-first order, the root being the node labeled 0.
+/// The tree is binary unless the optional parameter `arity` is set.
+///
 /// In directed graphs generated by this objects, all edges follow paths from the root to the leaves.
 ///
 /// Such factories can be created by passing `tree/n` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str),
 /// where `n` is the number of node and must be at least 1.
+/// The arity can be given using the `arity` optional parameter (e.g. `tree/n,arity=3`); it must be at least 1.
 #[derive(Default)]
 pub struct TreeGeneratorFactory;
 
@@ -26,6 +30,7 @@ where
             "A generator producing a tree.",
             "The first parameter gives the number of nodes.",
             "The tree is well balanced.",
+            "The tree is binary, unless the optional parameter \"arity\" is set.",
         ]
     }
 
@@ -33,31 +38,30 @@ where
         vec![ParameterType::PositiveInteger]
     }
 
+    fn optional_parameters(&self) -> Vec<(&'static str, ParameterType, ParameterValue)> {
+        vec![(
+            "arity",
+            ParameterType::PositiveInteger,
+            ParameterValue::PositiveInteger(2),
+        )]
+    }
+
     fn try_with_params(
         &self,
         parameter_values: Vec<ParameterValue>,
     ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a tree generator";
         let n = parameter_values[0].unwrap_usize();
-        Ok(Box::new(move |_| match n {
-            0 => Graph::default(),
-            1 => {
-                let mut g = Graph::with_capacity(1, 0);
-                g.new_node();
-                g
-            }
-            _ => {
-                let mut g = Graph::with_capacity(n, 0);
-                (0..n).for_each(|i| {
-                    if 2 * i + 1 < n {
-                        g.new_edge(i, 2 * i + 1);
-                    }
-
-                    if 2 * i + 2 < n {
-                        g.new_edge(i, 2 * i + 2);
-                    }
-                });
-                g
-            }
+        let arity = parameter_values[1].unwrap_usize();
+        if arity == 0 {
+            return Err(anyhow!(r#"optional parameter "arity" must be at least 1"#))
+                .context(context);
+        }
+        Ok(Box::new(move |_| {
+            let mut g = Graph::with_capacity(n, n.saturating_sub(1));
+            (0..n).for_each(|_| g.new_node());
+            (1..n).for_each(|i| g.new_edge((i - 1) / arity, i));
+            g
         }))
     }
 }
@@ -74,12 +78,16 @@ mod tests {
     use super::*;
     use crate::NodeIndexType;
     use petgraph::Directed;
+    use rand::rngs::ThreadRng;
 
     #[test]
     fn test_tree_of_zero() {
         let mut rng = rand::thread_rng();
         let g: Graph<Directed> = TreeGeneratorFactory
-            .try_with_params(vec![ParameterValue::PositiveInteger(0)])
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(0),
+                ParameterValue::PositiveInteger(2),
+            ])
             .unwrap()(&mut rng);
         assert_eq!(0, g.n_nodes());
         assert_eq!(0, g.n_edges());
@@ -89,7 +97,10 @@ mod tests {
     fn test_tree_of_one() {
         let mut rng = rand::thread_rng();
         let g: Graph<Directed> = TreeGeneratorFactory
-            .try_with_params(vec![ParameterValue::PositiveInteger(1)])
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(1),
+                ParameterValue::PositiveInteger(2),
+            ])
             .unwrap()(&mut rng);
         assert_eq!(1, g.n_nodes());
         assert_eq!(0, g.n_edges());
@@ -99,7 +110,10 @@ mod tests {
     fn test_tree() {
         let mut rng = rand::thread_rng();
         let g: Graph<Directed> = TreeGeneratorFactory
-            .try_with_params(vec![ParameterValue::PositiveInteger(4)])
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(4),
+                ParameterValue::PositiveInteger(2),
+            ])
             .unwrap()(&mut rng);
         assert_eq!(4, g.n_nodes());
         assert_eq!(
@@ -108,4 +122,30 @@ mod tests {
                 .collect::<Vec<(NodeIndexType, NodeIndexType)>>()
         );
     }
+
+    #[test]
+    fn test_ternary_tree() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Directed> = TreeGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(6),
+                ParameterValue::PositiveInteger(3),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(6, g.n_nodes());
+        assert_eq!(
+            vec![(0, 1), (0, 2), (0, 3), (1, 4), (1, 5)],
+            g.iter_edges()
+                .collect::<Vec<(NodeIndexType, NodeIndexType)>>()
+        );
+    }
+
+    #[test]
+    fn test_arity_is_zero() {
+        assert!((TreeGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(4),
+            ParameterValue::PositiveInteger(0)
+        ]) as Result<BoxedGenerator<Directed, ThreadRng>>)
+            .is_err())
+    }
 }
@@ -0,0 +1,160 @@
+use super::{BoxedGenerator, GeneratorFactory};
+use crate::{Graph, NamedParam, ParameterType, ParameterValue};
+use anyhow::{anyhow, Context, Result};
+use petgraph::EdgeType;
+use rand::{distributions::Uniform, prelude::Distribution, Rng};
+
+/// A factory used to build generators for graphs following the [Waxman](https://doi.org/10.1109/49.12889) model.
+///
+/// Nodes are placed uniformly at random in the unit square, and each pair of nodes at distance `d` is linked with probability `beta*exp(-d/(alpha*L))`,
+/// where `L` is the diameter of the unit square (square root of 2).
+///
+/// The position of each node is available through [`Graph::positions`].
+///
+/// In directed graphs generated by this objects, for each pair of nodes, both edges are considered for addition (0, 1 or 2 edges can be generated).
+///
+/// Such factories can be created by passing `waxman/n,a,b` to [`generators::generator_factory_from_str`](crate::generators#generator_factory_from_str) where
+///   - `n` is the number of nodes;
+///   - `a` (alpha) controls how fast the edge probability decreases with the distance (the higher, the slower);
+///   - `b` (beta) is the probability two nodes at the same place are linked.
+///
+/// Parameter `a` must be strictly positive.
+#[derive(Default)]
+pub struct WaxmanGeneratorFactory;
+
+impl<Ty, R> NamedParam<BoxedGenerator<Ty, R>> for WaxmanGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    fn name(&self) -> &'static str {
+        "waxman"
+    }
+
+    fn description(&self) -> Vec<&'static str> {
+        vec![
+            "A generator following the Waxman model in the unit square.",
+            "First parameter gives the number of nodes.",
+            "The second and third parameters (alpha and beta) set the edge probability to beta*exp(-d/(alpha*sqrt(2))) for nodes at distance d.",
+        ]
+    }
+
+    fn expected_parameter_types(&self) -> Vec<ParameterType> {
+        vec![
+            ParameterType::PositiveInteger,
+            ParameterType::PositiveFloat,
+            ParameterType::Probability,
+        ]
+    }
+
+    fn try_with_params(
+        &self,
+        parameter_values: Vec<ParameterValue>,
+    ) -> Result<BoxedGenerator<Ty, R>> {
+        let context = "while building a Waxman generator";
+        let n = parameter_values[0].unwrap_usize();
+        let alpha = parameter_values[1].unwrap_f64();
+        let beta = parameter_values[2].unwrap_f64();
+        if alpha == 0. {
+            return Err(anyhow!(
+                r#"second parameter ("a") must be strictly positive"#
+            ))
+            .context(context);
+        }
+        Ok(Box::new(move |r| build_graph(n, alpha, beta, r)))
+    }
+}
+
+fn build_graph<Ty, R>(n: usize, alpha: f64, beta: f64, r: &mut R) -> Graph<Ty>
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+    let positions = (0..n)
+        .map(|_| vec![r.gen::<f64>(), r.gen::<f64>()])
+        .collect::<Vec<Vec<f64>>>();
+    let mut g = Graph::with_capacity(n, 0);
+    (0..n).for_each(|_| g.new_node());
+    let proba_uniform = Uniform::new_inclusive(0., 1.);
+    let scale = alpha * std::f64::consts::SQRT_2;
+    for i in 0..n {
+        let first_candidate = if Ty::is_directed() { 0 } else { i + 1 };
+        for j in first_candidate..n {
+            if i == j {
+                continue;
+            }
+            let d = f64::hypot(
+                positions[i][0] - positions[j][0],
+                positions[i][1] - positions[j][1],
+            );
+            if proba_uniform.sample(r) < beta * (-d / scale).exp() {
+                g.new_edge(i, j);
+            }
+        }
+    }
+    g.set_positions(positions);
+    g
+}
+
+impl<Ty, R> GeneratorFactory<Ty, R> for WaxmanGeneratorFactory
+where
+    R: Rng,
+    Ty: EdgeType,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use petgraph::{Directed, Undirected};
+    use rand::rngs::ThreadRng;
+
+    #[test]
+    fn test_alpha_is_zero() {
+        assert!((WaxmanGeneratorFactory.try_with_params(vec![
+            ParameterValue::PositiveInteger(10),
+            ParameterValue::PositiveFloat(0.),
+            ParameterValue::Probability(0.5),
+        ]) as Result<BoxedGenerator<Directed, ThreadRng>>)
+            .is_err())
+    }
+
+    #[test]
+    fn test_beta_is_zero() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = WaxmanGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(10),
+                ParameterValue::PositiveFloat(1.),
+                ParameterValue::Probability(0.),
+            ])
+            .unwrap()(&mut rng);
+        assert_eq!(10, g.n_nodes());
+        assert_eq!(0, g.n_edges());
+        assert_eq!(10, g.positions().unwrap().len());
+    }
+
+    #[test]
+    fn test_short_edges_are_more_likely() {
+        let mut rng = rand::thread_rng();
+        let g: Graph<Undirected> = WaxmanGeneratorFactory
+            .try_with_params(vec![
+                ParameterValue::PositiveInteger(200),
+                ParameterValue::PositiveFloat(0.05),
+                ParameterValue::Probability(1.),
+            ])
+            .unwrap()(&mut rng);
+        let positions = g.positions().unwrap();
+        let mean_length = g
+            .iter_edges()
+            .map(|(a, b)| {
+                f64::hypot(
+                    positions[a][0] - positions[b][0],
+                    positions[a][1] - positions[b][1],
+                )
+            })
+            .sum::<f64>()
+            / g.n_edges() as f64;
+        assert!(mean_length < 0.3);
+    }
+}
@@ -69,20 +69,20 @@ where
 
 lazy_static! {
     pub(crate) static ref LINKERS_DIRECTED_PCG32: [Box<dyn Linker<Directed, Pcg32> + Sync>; 6] = [
-        Box::new(FirstToFirstLinker::default()),
-        Box::new(BidirectionalFirstToFirstLinker::default()),
+        Box::new(FirstToFirstLinker),
+        Box::new(BidirectionalFirstToFirstLinker),
         Box::new(MinIncomingLinker::default()),
         Box::new(BidirectionalMinIncomingLinker::default()),
-        Box::new(RandomLinker::default()),
-        Box::new(BidirectionalRandomLinker::default()),
+        Box::new(RandomLinker),
+        Box::new(BidirectionalRandomLinker),
     ];
 }
 
 lazy_static! {
     pub(crate) static ref LINKERS_UNDIRECTED_PCG32: [Box<dyn Linker<Undirected, Pcg32> + Sync>; 3] = [
-        Box::new(FirstToFirstLinker::default()),
+        Box::new(FirstToFirstLinker),
         Box::new(MinIncomingLinker::default()),
-        Box::new(RandomLinker::default()),
+        Box::new(RandomLinker),
     ];
 }
 